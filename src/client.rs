@@ -1,40 +1,78 @@
-use super::model::*;
+use super::error::{Error, Result};
+use super::model::{self, *};
 
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
+use chrono::{Duration, NaiveDate};
+use futures::stream::{self, Stream, TryStreamExt};
 use url::Url;
 use uuid::Uuid;
-//use simple_error::SimpleError;
-use reqwest;
 use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+use serde::Serialize;
 
+/// Historical market data items. Alpaca groups these by symbol, instead
+/// of repeating the symbol in every item.
+trait DataItem {
+    fn set_symbol(&mut self, symbol: &str);
+}
+
+impl DataItem for Bar {
+    fn set_symbol(&mut self, symbol: &str) {
+        self.symbol = symbol.to_string();
+    }
+}
+
+impl DataItem for Trade {
+    fn set_symbol(&mut self, symbol: &str) {
+        self.symbol = symbol.to_string();
+    }
+}
 
-// type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+impl DataItem for Quote {
+    fn set_symbol(&mut self, symbol: &str) {
+        self.symbol = symbol.to_string();
+    }
+}
 
+#[derive(Clone)]
 pub struct Client {
     /// todo: docs ...
     client: reqwest::Client,
     /// todo: docs ...
-    endpoint: Url,
+    pub(crate) endpoint: Url,
+    /// The base url of the market data api.
+    data_endpoint: Url,
+    /// The base url of the market data streams.
+    pub(crate) data_stream_endpoint: Url,
+    /// The alpaca key id, used to authenticate streams.
+    pub(crate) key_id: String,
+    /// The alpaca secret key, used to authenticate streams.
+    pub(crate) secret_key: String,
 }
 
 /// todo: docs ...
 pub struct ClientBuilder {
     /// todo: docs ...
     endpoint: Url,
+    /// The base url of the market data api.
+    data_endpoint: Url,
+    /// The base url of the market data streams.
+    data_stream_endpoint: Url,
     /// todo: docs ...
     key_id: Option<String>,
     /// todo: docs ...
     secret_key: Option<String>,
 }
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
-
 impl ClientBuilder {
 
     /// todo: docs ...
     pub fn new() -> Self {
         ClientBuilder {
             endpoint: Client::paper_endpoint(),
+            data_endpoint: Client::data_endpoint(),
+            data_stream_endpoint: Client::data_stream_endpoint(),
             key_id: None,
             secret_key: None,
         }
@@ -43,8 +81,10 @@ impl ClientBuilder {
     /// todo: docs ...
     pub fn build(self) -> Result<Client> {
         // Extract the secrets.
-        let key_id = self.key_id.ok_or("Missing alpaca key id")?;
-        let secret_key = self.secret_key.ok_or("Missing alpaca secret key")?;
+        let key_id = self.key_id
+            .ok_or_else(|| Error::Config("Missing alpaca key id".into()))?;
+        let secret_key = self.secret_key
+            .ok_or_else(|| Error::Config("Missing alpaca secret key".into()))?;
 
         // Setup the default headers with the secrets.
         let mut headers = HeaderMap::new();
@@ -59,8 +99,12 @@ impl ClientBuilder {
             .build()?;
 
         Ok(Client {
-            client: client,
+            client,
             endpoint: self.endpoint,
+            data_endpoint: self.data_endpoint,
+            data_stream_endpoint: self.data_stream_endpoint,
+            key_id,
+            secret_key,
         })
     }
 
@@ -73,6 +117,26 @@ impl ClientBuilder {
         Ok(self)
     }
 
+    /// Set the base url of the market data api. Defaults to
+    /// `Client::data_endpoint()`.
+    pub fn data_endpoint<U>(mut self, endpoint: U)
+            -> std::result::Result<Self, <U as TryInto<Url>>::Error>
+        where U: TryInto<Url>
+    {
+        self.data_endpoint = endpoint.try_into()?;
+        Ok(self)
+    }
+
+    /// Set the base url of the market data streams. Defaults to
+    /// `Client::data_stream_endpoint()`.
+    pub fn data_stream_endpoint<U>(mut self, endpoint: U)
+            -> std::result::Result<Self, <U as TryInto<Url>>::Error>
+        where U: TryInto<Url>
+    {
+        self.data_stream_endpoint = endpoint.try_into()?;
+        Ok(self)
+    }
+
     /// todo: docs ...
     pub fn key_id<T>(mut self, key_id: T) -> Self
         where T: Into<String>
@@ -105,6 +169,12 @@ impl Builder {
 }
 */
 
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Client {
     pub fn builder() -> ClientBuilder {
         ClientBuilder::new()
@@ -116,21 +186,47 @@ impl Client {
         Url::parse(endpoint).unwrap()
     }
 
+    /// The base url of alpaca's market data api.
+    pub fn data_endpoint() -> Url {
+        let endpoint = "https://data.alpaca.markets";
+        Url::parse(endpoint).unwrap()
+    }
+
+    /// The base url of alpaca's market data streams.
+    pub fn data_stream_endpoint() -> Url {
+        let endpoint = "wss://stream.data.alpaca.markets";
+        Url::parse(endpoint).unwrap()
+    }
+
     async fn handle_request<T>(request: reqwest::RequestBuilder) -> Result<T>
         where for<'de> T: serde::Deserialize<'de>
     {
         let response = request.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        // Alpaca describes most rejections with a json error object, but
+        // gateways and load balancers in front of the api may answer with
+        // html pages or nothing at all. Only trust the body when it
+        // actually decodes as an alpaca error.
+        if !status.is_success() {
+            return match serde_json::from_str::<model::Error>(&body) {
+                Ok(error) => Err(Error::Api { status, error }),
+                Err(_) => Err(Error::Http { status, headers, body }),
+            };
+        }
 
-        // This should be clarified a little more. Which status
-        // codes return a json object, and which do not? Right now,
-        // assuming that anything non-2xx returns a json object with
-        // error information may mask internal server errors and other
-        // connection problems.
+        // Endpoints such as order cancellation reply with 204 and an empty
+        // body. Decode these as json `null`, which is what `()` and
+        // `Option<T>` expect.
+        let json = match status == StatusCode::NO_CONTENT || body.trim().is_empty() {
+            true  => "null",
+            false => body.as_str(),
+        };
 
-        match response.status().is_success() {
-            true  => Ok(response.json::<T>().await?),
-            false => Err(Box::new(response.json::<Error>().await?)),
-        }
+        serde_json::from_str(json)
+            .map_err(|source| Error::Decode { source, body })
     }
 
     pub async fn get_account(&self) -> Result<Account> {
@@ -138,9 +234,110 @@ impl Client {
         Self::handle_request(self.client.get(path)).await
     }
 
+    pub async fn get_account_configurations(&self) -> Result<AccountConfigurations> {
+        let path = self.endpoint.join("/v2/account/configurations")?;
+        Self::handle_request(self.client.get(path)).await
+    }
+
+    /// Change the account configurations, and return the updated
+    /// settings.
+    pub async fn update_account_configurations(&self, args: &AccountConfigurationsUpdate)
+            -> Result<AccountConfigurations>
+    {
+        let path = self.endpoint.join("/v2/account/configurations")?;
+        Self::handle_request(self.client.patch(path).json(args)).await
+    }
+
+    /// Fetch the value of the account over time.
+    pub async fn get_portfolio_history(&self, query: &PortfolioHistoryQuery)
+            -> Result<PortfolioHistory>
+    {
+        let path = self.endpoint.join("/v2/account/portfolio/history")?;
+        Self::handle_request(self.client.get(path).query(query)).await
+    }
+
     pub async fn get_orders(&self) -> Result<Vec<Order>> {
+        self.query_orders(&OrderQuery::new()).await
+    }
+
+    /// Fetch a single page of orders matching `query`.
+    pub async fn query_orders(&self, query: &OrderQuery) -> Result<Vec<Order>> {
         let path = self.endpoint.join("/v2/orders")?;
-        Self::handle_request(self.client.get(path)).await
+        Self::handle_request(self.client.get(path).query(query)).await
+    }
+
+    /// Walk every order matching `query`, fetching further pages as the
+    /// stream is consumed. The query's `limit` is used as the page size,
+    /// up to `OrderQuery::MAX_LIMIT`.
+    ///
+    /// Pages are chained by submission time: each request continues from
+    /// the last order of the previous page, in the query's direction. If
+    /// a whole page of orders shares one submission time, the stream
+    /// cannot move past it, and fails with `Error::Pagination`.
+    pub fn stream_orders(&self, query: OrderQuery)
+            -> impl Stream<Item = Result<Order>> + '_
+    {
+        let state = Some((query, HashSet::new()));
+
+        stream::try_unfold(state, move |state| async move {
+            match state {
+                Some((query, seen)) => self.next_orders_page(query, seen).await,
+                None => Ok(None),
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Fetch a single page of account activities matching `query`.
+    pub async fn get_activities(&self, query: &ActivityQuery) -> Result<Vec<Activity>> {
+        let path = self.endpoint.join("/v2/account/activities")?;
+        Self::handle_request(self.client.get(path).query(query)).await
+    }
+
+    /// Walk every account activity matching `query`, fetching further
+    /// pages as the stream is consumed. The query's `page_size` is used
+    /// as the page size.
+    pub fn stream_activities(&self, query: ActivityQuery)
+            -> impl Stream<Item = Result<Activity>> + '_
+    {
+        stream::try_unfold(Some(query), move |query| async move {
+            match query {
+                Some(query) => self.next_activities_page(query).await,
+                None => Ok(None),
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Fetch a page of activities, and compute the query for the page
+    /// after that.
+    async fn next_activities_page(&self, mut query: ActivityQuery)
+            -> Result<Option<(Vec<Activity>, Option<ActivityQuery>)>>
+    {
+        let page = self.get_activities(&query).await?;
+        let size = query.page_size.unwrap_or(ActivityQuery::DEFAULT_PAGE_SIZE);
+
+        // Each page continues from the id of the last activity returned.
+        let next = match (page.len() >= size as usize, page.last()) {
+            (true, Some(last)) => {
+                query.page_token = Some(last.id().to_string());
+                Some(query)
+            },
+            _ => None,
+        };
+
+        Ok(Some((page, next)))
+    }
+
+    /// Fetch the page of orders following `seen`, and compute the query
+    /// for the page after that.
+    async fn next_orders_page(&self, query: OrderQuery, seen: HashSet<Uuid>)
+            -> Result<Option<(Vec<Order>, NextOrders)>>
+    {
+        let raw = self.query_orders(&query).await?;
+        orders_page(query, seen, raw).map(Some)
     }
 
     pub async fn get_order(&self, id: &Uuid) -> Result<Order> {
@@ -151,7 +348,31 @@ impl Client {
         Self::handle_request(self.client.get(path)).await
     }
 
+    /// Fetch an order by the `client_order_id` it was submitted with.
+    pub async fn get_order_by_client_order_id(&self, client_order_id: &str)
+            -> Result<Order>
+    {
+        let path = self.endpoint.join("/v2/orders:by_client_order_id")?;
+        let request = self.client
+            .get(path)
+            .query(&[("client_order_id", client_order_id)]);
+
+        Self::handle_request(request).await
+    }
+
+    /// Submit an order. Orders that fail `OrderRequest::validate` are not
+    /// sent, and fail with `Error::InvalidOrder`.
     pub async fn request_order(&self, req: &OrderRequest) -> Result<Order> {
+        req.validate().map_err(Error::InvalidOrder)?;
+        self.request_order_unchecked(req).await
+    }
+
+    /// Submit an order without validating it first, leaving every check
+    /// to alpaca. Values that could not be converted to decimals are
+    /// still refused with `Error::InvalidOrder`, since they cannot be sent.
+    pub async fn request_order_unchecked(&self, req: &OrderRequest) -> Result<Order> {
+        req.check_numbers().map_err(Error::InvalidOrder)?;
+
         let request = self.client
             .post(self.endpoint.join("/v2/orders")?)
             .json(req);
@@ -159,7 +380,11 @@ impl Client {
         Self::handle_request(request).await
     }
 
+    /// Replace an open order. Requests that fail `OrderReplace::validate`
+    /// are not sent, and fail with `Error::InvalidOrder`.
     pub async fn replace_order(&self, id: Uuid, args: &OrderReplace) -> Result<Order> {
+        args.validate().map_err(Error::InvalidOrder)?;
+
         let path = self.endpoint
             .join("/v2/orders/")?
             .join(&id.to_hyphenated_ref().to_string())?;
@@ -179,11 +404,472 @@ impl Client {
         Self::handle_request(self.client.delete(path)).await
     }
 
-    /// todo: mult-response??
-    pub async fn cancel_all_orders(&self) -> Result<()> {
+    /// Cancel every open order. Alpaca attempts each cancellation
+    /// separately, and reports the outcome for each order.
+    pub async fn cancel_all_orders(&self) -> Result<Vec<OrderCancelation>> {
         let request = self.client
             .delete(self.endpoint.join("/v2/orders")?);
-        request.send().await?;
-        Ok(())
+
+        Self::handle_request(request).await
+    }
+
+    /// List all open positions.
+    pub async fn get_positions(&self) -> Result<Vec<Position>> {
+        let path = self.endpoint.join("/v2/positions")?;
+        Self::handle_request(self.client.get(path)).await
+    }
+
+    /// Fetch the open position for a symbol or asset id.
+    pub async fn get_position(&self, symbol: &str) -> Result<Position> {
+        let path = self.endpoint
+            .join("/v2/positions/")?
+            .join(symbol)?;
+
+        Self::handle_request(self.client.get(path)).await
+    }
+
+    /// Liquidate all or part of the position for a symbol or asset id.
+    /// Returns the order placed to close the position. Requests that fail
+    /// `ClosePosition::validate` are not sent, and fail with
+    /// `Error::InvalidOrder`.
+    pub async fn close_position(&self, symbol: &str, args: &ClosePosition)
+            -> Result<Order>
+    {
+        args.validate().map_err(Error::InvalidOrder)?;
+
+        let path = self.endpoint
+            .join("/v2/positions/")?
+            .join(symbol)?;
+
+        let request = self.client
+            .delete(path)
+            .query(args);
+
+        Self::handle_request(request).await
+    }
+
+    /// Liquidate every open position. If `cancel_orders` is true, open
+    /// orders are canceled before the positions are closed. Alpaca
+    /// attempts to close each position separately, and reports the
+    /// outcome for each one.
+    pub async fn close_all_positions(&self, cancel_orders: bool)
+            -> Result<Vec<PositionClosure>>
+    {
+        let request = self.client
+            .delete(self.endpoint.join("/v2/positions")?)
+            .query(&[("cancel_orders", cancel_orders)]);
+
+        Self::handle_request(request).await
+    }
+
+    /// List assets matching `query`.
+    pub async fn get_assets(&self, query: &AssetQuery) -> Result<Vec<Asset>> {
+        let path = self.endpoint.join("/v2/assets")?;
+        Self::handle_request(self.client.get(path).query(query)).await
+    }
+
+    /// Fetch an asset by symbol.
+    pub async fn get_asset(&self, symbol: &str) -> Result<Asset> {
+        let path = self.endpoint
+            .join("/v2/assets/")?
+            .join(symbol)?;
+
+        Self::handle_request(self.client.get(path)).await
+    }
+
+    /// Fetch an asset by id.
+    pub async fn get_asset_by_id(&self, id: &Uuid) -> Result<Asset> {
+        self.get_asset(&id.to_hyphenated_ref().to_string()).await
+    }
+
+    /// List the account's watchlists. The assets of each watchlist are
+    /// not included; use `get_watchlist` for those.
+    pub async fn get_watchlists(&self) -> Result<Vec<Watchlist>> {
+        let path = self.endpoint.join("/v2/watchlists")?;
+        Self::handle_request(self.client.get(path)).await
+    }
+
+    pub async fn create_watchlist(&self, args: &WatchlistRequest) -> Result<Watchlist> {
+        let path = self.endpoint.join("/v2/watchlists")?;
+        Self::handle_request(self.client.post(path).json(args)).await
+    }
+
+    pub async fn get_watchlist(&self, id: &Uuid) -> Result<Watchlist> {
+        Self::handle_request(self.client.get(self.watchlist_url(id)?)).await
+    }
+
+    /// Rename a watchlist, and replace its content if `args.symbols` is
+    /// set.
+    pub async fn update_watchlist(&self, id: &Uuid, args: &WatchlistRequest)
+            -> Result<Watchlist>
+    {
+        let request = self.client
+            .put(self.watchlist_url(id)?)
+            .json(args);
+
+        Self::handle_request(request).await
+    }
+
+    /// Append a symbol to a watchlist.
+    pub async fn add_to_watchlist(&self, id: &Uuid, symbol: &str) -> Result<Watchlist> {
+        let request = self.client
+            .post(self.watchlist_url(id)?)
+            .json(&serde_json::json!({ "symbol": symbol }));
+
+        Self::handle_request(request).await
+    }
+
+    /// Remove a symbol from a watchlist.
+    pub async fn remove_from_watchlist(&self, id: &Uuid, symbol: &str)
+            -> Result<Watchlist>
+    {
+        let path = self.endpoint
+            .join("/v2/watchlists/")?
+            .join(&format!("{}/", id.to_hyphenated_ref()))?
+            .join(symbol)?;
+
+        Self::handle_request(self.client.delete(path)).await
+    }
+
+    pub async fn delete_watchlist(&self, id: &Uuid) -> Result<()> {
+        Self::handle_request(self.client.delete(self.watchlist_url(id)?)).await
+    }
+
+    fn watchlist_url(&self, id: &Uuid) -> Result<Url> {
+        let url = self.endpoint
+            .join("/v2/watchlists/")?
+            .join(&id.to_hyphenated_ref().to_string())?;
+
+        Ok(url)
+    }
+
+    /// Fetch the market clock.
+    pub async fn get_clock(&self) -> Result<Clock> {
+        let path = self.endpoint.join("/v2/clock")?;
+        Self::handle_request(self.client.get(path)).await
+    }
+
+    /// List the trading days between `start` and `end`, inclusive.
+    pub async fn get_calendar(&self, start: NaiveDate, end: NaiveDate)
+            -> Result<Vec<CalendarDay>>
+    {
+        let request = self.client
+            .get(self.endpoint.join("/v2/calendar")?)
+            .query(&[("start", start.to_string()), ("end", end.to_string())]);
+
+        Self::handle_request(request).await
+    }
+
+    /// Stream historical bars matching `query`, fetching further pages as
+    /// the stream is consumed. Bars are grouped by symbol within each
+    /// page.
+    pub fn stream_bars(&self, query: BarsQuery) -> impl Stream<Item = Result<Bar>> + '_ {
+        self.stream_data("/v2/stocks/bars", query)
+    }
+
+    /// Stream historical trades matching `query`, fetching further pages
+    /// as the stream is consumed.
+    pub fn stream_trades(&self, query: DataQuery) -> impl Stream<Item = Result<Trade>> + '_ {
+        self.stream_data("/v2/stocks/trades", query)
+    }
+
+    /// Stream historical quotes matching `query`, fetching further pages
+    /// as the stream is consumed.
+    pub fn stream_quotes(&self, query: DataQuery) -> impl Stream<Item = Result<Quote>> + '_ {
+        self.stream_data("/v2/stocks/quotes", query)
+    }
+
+    /// Walk the pages of a historical data endpoint.
+    fn stream_data<T, Q>(&self, path: &'static str, query: Q)
+            -> impl Stream<Item = Result<T>> + '_
+        where for<'de> T: serde::Deserialize<'de> + DataItem + 'static,
+              Q: Serialize + 'static,
+    {
+        // The first page has no token. Once a page comes back without a
+        // token for the next one, the state becomes `None`.
+        let state = Some((query, None));
+
+        stream::try_unfold(state, move |state| async move {
+            match state {
+                Some((query, token)) => self.next_data_page(path, query, token).await,
+                None => Ok(None),
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Fetch one page of historical data.
+    async fn next_data_page<T, Q>(&self, path: &str, query: Q, token: Option<String>)
+            -> Result<Option<(Vec<T>, Option<(Q, Option<String>)>)>>
+        where for<'de> T: serde::Deserialize<'de> + DataItem,
+              Q: Serialize,
+    {
+        let mut request = self.client
+            .get(self.data_endpoint.join(path)?)
+            .query(&query);
+
+        if let Some(token) = &token {
+            request = request.query(&[("page_token", token)]);
+        }
+
+        let page: DataPage<T> = Self::handle_request(request).await?;
+        let items = page.items
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(symbol, items)| {
+                items.into_iter().map(move |mut item| {
+                    item.set_symbol(&symbol);
+                    item
+                })
+            })
+            .collect();
+
+        let next = page.next_page_token.map(|token| (query, Some(token)));
+        Ok(Some((items, next)))
+    }
+
+    /// Fetch the latest trade of `symbol`.
+    pub async fn get_latest_trade(&self, symbol: &str) -> Result<Trade> {
+        let path = format!("/v2/stocks/{}/trades/latest", symbol);
+        let request = self.client.get(self.data_endpoint.join(&path)?);
+
+        let mut latest: LatestTrade = Self::handle_request(request).await?;
+        latest.trade.set_symbol(&latest.symbol);
+        Ok(latest.trade)
+    }
+
+    /// Fetch the latest quote of `symbol`.
+    pub async fn get_latest_quote(&self, symbol: &str) -> Result<Quote> {
+        let path = format!("/v2/stocks/{}/quotes/latest", symbol);
+        let request = self.client.get(self.data_endpoint.join(&path)?);
+
+        let mut latest: LatestQuote = Self::handle_request(request).await?;
+        latest.quote.set_symbol(&latest.symbol);
+        Ok(latest.quote)
+    }
+
+    /// Fetch the latest minute bar of `symbol`.
+    pub async fn get_latest_bar(&self, symbol: &str) -> Result<Bar> {
+        let path = format!("/v2/stocks/{}/bars/latest", symbol);
+        let request = self.client.get(self.data_endpoint.join(&path)?);
+
+        let mut latest: LatestBar = Self::handle_request(request).await?;
+        latest.bar.set_symbol(&latest.symbol);
+        Ok(latest.bar)
+    }
+
+    /// Fetch snapshots of `symbols`, keyed by symbol. Symbols without any
+    /// market data are left out.
+    pub async fn get_snapshots<I, T>(&self, symbols: I) -> Result<HashMap<String, Snapshot>>
+        where I: IntoIterator<Item = T>,
+              T: Into<String>,
+    {
+        let symbols = symbols.into_iter()
+            .map(Into::into)
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let request = self.client
+            .get(self.data_endpoint.join("/v2/stocks/snapshots")?)
+            .query(&[("symbols", symbols)]);
+
+        let snapshots: HashMap<String, Option<Snapshot>> =
+            Self::handle_request(request).await?;
+
+        Ok(snapshots.into_iter()
+            .filter_map(|(symbol, snapshot)| {
+                let mut snapshot = snapshot?;
+                let bars = vec![
+                    &mut snapshot.minute_bar,
+                    &mut snapshot.daily_bar,
+                    &mut snapshot.prev_daily_bar,
+                ];
+                for bar in bars.into_iter().flatten() {
+                    bar.set_symbol(&symbol);
+                }
+                if let Some(trade) = &mut snapshot.latest_trade {
+                    trade.set_symbol(&symbol);
+                }
+                if let Some(quote) = &mut snapshot.latest_quote {
+                    quote.set_symbol(&symbol);
+                }
+                Some((symbol, snapshot))
+            })
+            .collect())
+    }
+}
+
+/// The query of the next page of orders, and the orders that may show up
+/// on it again.
+type NextOrders = Option<(OrderQuery, HashSet<Uuid>)>;
+
+/// Drop the orders of `raw`, the response to `query`, that were `seen` on
+/// the previous page, and compute the query and seen orders of the next
+/// page.
+fn orders_page(mut query: OrderQuery, seen: HashSet<Uuid>, raw: Vec<Order>)
+        -> Result<(Vec<Order>, NextOrders)>
+{
+    // Alpaca caps the page size, so a page can be full below `limit`.
+    let limit = std::cmp::min(
+        query.limit.unwrap_or(OrderQuery::DEFAULT_LIMIT),
+        OrderQuery::MAX_LIMIT);
+    let full = raw.len() >= limit as usize;
+    let submitted = |order: &Order| order.submitted_at.unwrap_or(order.created_at);
+
+    // The next page starts at the timestamp of the last order returned,
+    // inclusively, so that orders sharing that timestamp are not lost.
+    // Only those orders can show up again, so they are the ones
+    // remembered, whether or not they were already seen before.
+    let next = match (full, raw.last().map(submitted)) {
+        (true, Some(boundary)) => {
+            let step = Duration::nanoseconds(1);
+            let (bound, start) = match query.direction {
+                Some(SortDirection::Asc) => (&mut query.after, boundary - step),
+                _ => (&mut query.until, boundary + step),
+            };
+
+            // When the whole page shares the timestamp it started at,
+            // the next request is the same as this one. Keep what was
+            // seen before too, and give up once nothing new turns up.
+            let stuck = *bound == Some(start);
+            if stuck && raw.iter().all(|order| seen.contains(&order.id)) {
+                return Err(Error::Pagination(format!(
+                    "{} or more orders were submitted at {}, more than \
+                     fit in one page", limit, boundary)));
+            }
+
+            *bound = Some(start);
+            let mut next_seen: HashSet<Uuid> = raw.iter()
+                .filter(|order| submitted(order) == boundary)
+                .map(|order| order.id)
+                .collect();
+            if stuck {
+                next_seen.extend(seen.iter().copied());
+            }
+            Some((query, next_seen))
+        },
+        _ => None,
+    };
+
+    let page = raw.into_iter()
+        .filter(|order| !seen.contains(&order.id))
+        .collect();
+
+    Ok((page, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde_json::json;
+
+    use super::*;
+
+    /// Orders submitted at `seconds`, with ids counting up from 1. Orders
+    /// at the same second share a submission time.
+    fn orders(seconds: &[i64]) -> Vec<Order> {
+        seconds.iter().enumerate()
+            .map(|(i, &second)| model::test_order(json!({
+                "id": Uuid::from_u128(i as u128 + 1).to_string(),
+                "submitted_at": Utc.timestamp_opt(second, 0).unwrap().to_rfc3339(),
+            })))
+            .collect()
+    }
+
+    fn submitted(order: &Order) -> DateTime<Utc> {
+        order.submitted_at.unwrap()
+    }
+
+    /// Answer `query` the way alpaca does: `after` and `until` are
+    /// exclusive, and pages are capped at `OrderQuery::MAX_LIMIT`.
+    fn serve(all: &[Order], query: &OrderQuery) -> Vec<Order> {
+        let limit = std::cmp::min(
+            query.limit.unwrap_or(OrderQuery::DEFAULT_LIMIT),
+            OrderQuery::MAX_LIMIT);
+
+        let mut matching: Vec<Order> = all.iter()
+            .filter(|order| query.after.is_none_or(|after| submitted(order) > after))
+            .filter(|order| query.until.is_none_or(|until| submitted(order) < until))
+            .cloned()
+            .collect();
+
+        // Orders are sorted by submission time only, so the order of ties
+        // is up to the server.
+        matching.sort_by_key(submitted);
+        if !matches!(query.direction, Some(SortDirection::Asc)) {
+            matching.reverse();
+        }
+
+        matching.truncate(limit as usize);
+        matching
+    }
+
+    /// Walk every page of `query`, returning the ids of the orders in
+    /// the order they were yielded.
+    fn walk(all: &[Order], query: OrderQuery) -> Result<Vec<Uuid>> {
+        let mut ids = Vec::new();
+        let mut next = Some((query, HashSet::new()));
+
+        while let Some((query, seen)) = next {
+            let raw = serve(all, &query);
+            let (page, after) = orders_page(query, seen, raw)?;
+            ids.extend(page.iter().map(|order| order.id));
+            next = after;
+        }
+
+        Ok(ids)
+    }
+
+    fn ids(range: &[u128]) -> Vec<Uuid> {
+        range.iter().map(|&id| Uuid::from_u128(id)).collect()
+    }
+
+    #[test]
+    fn pages_through_distinct_times() {
+        let all = orders(&[5, 4, 3, 2, 1]);
+        let found = walk(&all, OrderQuery::new().limit(2)).unwrap();
+        assert_eq!(found, ids(&[1, 2, 3, 4, 5]));
+
+        let found = walk(&all, OrderQuery::new().limit(2).direction(SortDirection::Asc)).unwrap();
+        assert_eq!(found, ids(&[5, 4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn pages_through_shared_times() {
+        let all = orders(&[3, 2, 2, 2, 1]);
+
+        let mut found = walk(&all, OrderQuery::new().limit(4)).unwrap();
+        found.sort();
+        assert_eq!(found, ids(&[1, 2, 3, 4, 5]));
+
+        let mut found = walk(&all, OrderQuery::new().limit(4).direction(SortDirection::Asc))
+            .unwrap();
+        found.sort();
+        assert_eq!(found, ids(&[1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn fails_when_a_page_shares_one_time() {
+        let all = orders(&[3, 2, 2, 2, 1]);
+
+        for &limit in [2, 3].iter() {
+            match walk(&all, OrderQuery::new().limit(limit)) {
+                Err(Error::Pagination(_)) => {},
+                other => panic!("limit {}: {:?}", limit, other.map_err(|e| e.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn caps_the_page_size() {
+        assert_eq!(OrderQuery::new().limit(1000).limit, Some(OrderQuery::MAX_LIMIT));
+
+        let seconds: Vec<i64> = (0..600).rev().collect();
+        let all = orders(&seconds);
+
+        let mut query = OrderQuery::new();
+        query.limit = Some(1000);
+        assert_eq!(walk(&all, query).unwrap().len(), 600);
     }
 }
@@ -0,0 +1,155 @@
+use std::error;
+use std::fmt;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+use super::model;
+
+/// Result type returned by the `Client`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The ways in which a `Client` call can fail.
+#[derive(Debug)]
+pub enum Error {
+    /// Alpaca rejected the request and described why in the response
+    /// body.
+    Api {
+        /// HTTP status of the response.
+        status: StatusCode,
+        /// The error returned by Alpaca.
+        error: model::Error,
+    },
+    /// The server answered with a non-2xx status, but the body was not
+    /// an alpaca error. This happens with internal server errors, gateway
+    /// html pages and empty responses.
+    Http {
+        /// HTTP status of the response.
+        status: StatusCode,
+        /// Headers of the response.
+        headers: HeaderMap,
+        /// The raw response body.
+        body: String,
+    },
+    /// The request could not be sent, or the response could not be
+    /// received.
+    Transport(reqwest::Error),
+    /// The response body could not be decoded.
+    Decode {
+        /// The underlying json error.
+        source: serde_json::Error,
+        /// The raw response body.
+        body: String,
+    },
+    /// A message for a stream could not be encoded.
+    Encode(serde_json::Error),
+    /// The websocket connection of a stream failed.
+    WebSocket(tokio_tungstenite::tungstenite::Error),
+    /// Alpaca refused the credentials of a stream. Reconnecting will not
+    /// help, so the stream ends.
+    StreamRefused(String),
+    /// A stream reported an error, or the server broke the stream
+    /// protocol.
+    Stream(String),
+    /// A request url could not be constructed.
+    Url(url::ParseError),
+    /// The client was built with missing or invalid settings.
+    Config(String),
+    /// A paginated listing could not make progress past a page.
+    Pagination(String),
+    /// An order was not sent, because alpaca would reject it, or one of
+    /// its values could not be converted to a decimal.
+    InvalidOrder(Vec<model::OrderViolation>),
+}
+
+impl Error {
+    /// The error returned by Alpaca, if the request was rejected by the
+    /// api itself.
+    pub fn api_error(&self) -> Option<&model::Error> {
+        match self {
+            Error::Api { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Api { status, error } =>
+                write!(f, "alpaca api error [{}]: {}", status, error),
+            Error::Http { status, body, .. } if body.trim().is_empty() =>
+                write!(f, "http error [{}]", status),
+            Error::Http { status, body, .. } =>
+                write!(f, "http error [{}]: {}", status, body),
+            Error::Transport(e) =>
+                write!(f, "transport error: {}", e),
+            Error::Decode { source, .. } =>
+                write!(f, "failed to decode response: {}", source),
+            Error::Encode(e) =>
+                write!(f, "failed to encode message: {}", e),
+            Error::WebSocket(e) =>
+                write!(f, "websocket error: {}", e),
+            Error::StreamRefused(message) =>
+                write!(f, "stream refused: {}", message),
+            Error::Stream(message) =>
+                write!(f, "stream error: {}", message),
+            Error::Url(e) =>
+                write!(f, "invalid url: {}", e),
+            Error::Config(message) =>
+                write!(f, "invalid client configuration: {}", message),
+            Error::Pagination(message) =>
+                write!(f, "pagination error: {}", message),
+            Error::InvalidOrder(violations) => {
+                write!(f, "invalid order")?;
+                for (i, violation) in violations.iter().enumerate() {
+                    let sep = if i == 0 { ":" } else { ";" };
+                    write!(f, "{} {}", sep, violation)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Api { error, .. } => Some(error),
+            Error::Transport(e) => Some(e),
+            Error::Decode { source, .. } => Some(source),
+            Error::Encode(e) => Some(e),
+            Error::WebSocket(e) => Some(e),
+            Error::Url(e) => Some(e),
+            Error::Http { .. }
+            | Error::StreamRefused(_)
+            | Error::Stream(_)
+            | Error::Config(_)
+            | Error::Pagination(_)
+            | Error::InvalidOrder(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Transport(e)
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(e)
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Self {
+        Error::Url(e)
+    }
+}
+
+impl From<reqwest::header::InvalidHeaderValue> for Error {
+    fn from(e: reqwest::header::InvalidHeaderValue) -> Self {
+        Error::Config(e.to_string())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod client;
+pub mod error;
+pub mod model;
+pub mod stream;
+
+pub use client::{Client, ClientBuilder};
+pub use error::{Error, Result};
+pub use stream::{MarketDataStream, TradeUpdates};
@@ -1,13 +1,6 @@
-mod client;
-mod model;
-
-use model::*;
-
-use serde_json;
-use client::Client;
+use alpaca_rs::model::*;
+use alpaca_rs::Client;
 use std::error::Error;
-use uuid::Uuid;
-use rug::Float;
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
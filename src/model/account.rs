@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
-use rug::Float;
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 
+use super::Decimal;
+
 /// The following are the possible account status values. Most likely,
 /// the account status is `Active` unless there is any problem. The account
 /// status may get in `AccountUpdated` when personal information is being
@@ -48,8 +49,8 @@ pub struct Account {
     /// Currency code. For example "USD".
     pub currency: String,
     /// Cash balance.
-    #[serde(with = "super::serde::float")]
-    pub cash: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub cash: Decimal,
     /// Whether or not the account has been flagged as a pattern day trader.
     pub pattern_day_trader: bool,
     /// User setting. If true, the account is not allowed to place orders.
@@ -65,53 +66,133 @@ pub struct Account {
     /// Flag to denote whether or not the account is permitted to short.
     pub shorting_enabled: bool,
     /// Real-time MtM value of all long positions held in the account.
-    #[serde(with = "super::serde::float")]
-    pub long_market_value: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub long_market_value: Decimal,
     /// Real-time MtM value of all short positions held in the account.
-    #[serde(with = "super::serde::float")]
-    pub short_market_value: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub short_market_value: Decimal,
     /// Cash + long_market_value + short_market_value.
-    #[serde(with = "super::serde::float")]
-    pub equity: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub equity: Decimal,
     /// Equity as of previous trading day at 16:00:00 ET.
-    #[serde(with = "super::serde::float")]
-    pub last_equity: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub last_equity: Decimal,
     /// Buying power multiplier that represents account margin
     /// classification; valid values 1 (standard limited margin account
     /// with 1x buying power), 2 (reg T margin account with 2x intraday
     /// and overnight buying power; this is the default for all non-PDT
     /// accounts with $2,000 or more equity), 4 (PDT account with 4x
     /// intraday buying power and 2x reg T overnight buying power)
-    #[serde(with = "super::serde::float")]
-    pub multiplier: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub multiplier: Decimal,
     /// Current available $ buying power; If multiplier = 4, this is
     /// your daytrade buying power which is calculated as (last_equity -
     /// (last) maintenance_margin) * 4; If multiplier = 2, buying_power
     /// = max(equity – initial_margin,0) * 2; If multiplier = 1,
     /// buying_power = cash.
-    #[serde(with = "super::serde::float")]
-    pub buying_power: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub buying_power: Decimal,
     /// Reg T initial margin requirement (continuously updated value).
-    #[serde(with = "super::serde::float")]
-    pub initial_margin: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub initial_margin: Decimal,
     /// Maintenance margin requirement (continuously updated value).
-    #[serde(with = "super::serde::float")]
-    pub maintenance_margin: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub maintenance_margin: Decimal,
     /// Value of special memorandum account (will be used at a later
     /// date to provide additional buying_power).
-    #[serde(with = "super::serde::float")]
-    pub sma: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub sma: Decimal,
     /// The current number of daytrades that have been made in the last
     /// 5 trading days (inclusive of today).
     pub daytrade_count: i64,
     /// Your maintenance margin requirement on the previous trading day.
-    #[serde(with = "super::serde::float")]
-    pub last_maintenance_margin: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub last_maintenance_margin: Decimal,
     /// Your buying power for day trades (continuously updated value).
-    #[serde(with = "super::serde::float")]
-    pub daytrading_buying_power: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub daytrading_buying_power: Decimal,
     /// Your buying power under Regulation T (your excess equity -
     /// equity minus margin value - times your margin multiplier).
-    #[serde(with = "super::serde::float")]
-    pub regt_buying_power: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub regt_buying_power: Decimal,
+}
+
+/// Which day trade buying power checks are run on orders.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DtbpCheck {
+    /// Check both entry and exit orders.
+    Both,
+    /// Check entry orders only.
+    Entry,
+    /// Check exit orders only.
+    Exit,
+}
+
+/// Which trade confirmation emails are sent.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeConfirmEmail {
+    /// Send an email for every trade.
+    All,
+    /// Do not send trade confirmation emails.
+    None,
+}
+
+/// User settings that control how the account trades.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AccountConfigurations {
+    /// Which orders day trade buying power is checked for.
+    pub dtbp_check: DtbpCheck,
+    /// If true, the account is not allowed to open short positions.
+    pub no_shorting: bool,
+    /// If true, the account is not allowed to place new orders. This is
+    /// reported as `Account::trade_suspended_by_user`.
+    pub suspend_trade: bool,
+    /// Which trade confirmation emails are sent.
+    pub trade_confirm_email: TradeConfirmEmail,
+}
+
+/// Changes to the account configurations. Settings that are left out
+/// are not changed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AccountConfigurationsUpdate {
+    /// Which orders day trade buying power is checked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtbp_check: Option<DtbpCheck>,
+    /// If true, the account is not allowed to open short positions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_shorting: Option<bool>,
+    /// If true, the account is not allowed to place new orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspend_trade: Option<bool>,
+    /// Which trade confirmation emails are sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_confirm_email: Option<TradeConfirmEmail>,
+}
+
+impl AccountConfigurationsUpdate {
+    pub fn new() -> AccountConfigurationsUpdate {
+        AccountConfigurationsUpdate::default()
+    }
+
+    pub fn dtbp_check(mut self, dtbp_check: DtbpCheck) -> Self {
+        self.dtbp_check = Some(dtbp_check);
+        self
+    }
+
+    pub fn no_shorting(mut self, no_shorting: bool) -> Self {
+        self.no_shorting = Some(no_shorting);
+        self
+    }
+
+    pub fn suspend_trade(mut self, suspend_trade: bool) -> Self {
+        self.suspend_trade = Some(suspend_trade);
+        self
+    }
+
+    pub fn trade_confirm_email(mut self, trade_confirm_email: TradeConfirmEmail) -> Self {
+        self.trade_confirm_email = Some(trade_confirm_email);
+        self
+    }
 }
@@ -0,0 +1,269 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Float, Side, SortDirection};
+
+/// The kind of an account activity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ActivityType {
+    /// Order fills (both partial and full fills).
+    Fill,
+    /// Cash transactions (both CSD and CSW).
+    Trans,
+    /// Miscellaneous or rarely used activity types (all types except
+    /// those in TRANS, DIV, or FILL).
+    Misc,
+    /// ACATS IN/OUT (Cash).
+    Acatc,
+    /// ACATS IN/OUT (Securities).
+    Acats,
+    /// Cash deposit(+).
+    Csd,
+    /// Cash withdrawal(-).
+    Csw,
+    /// Dividends.
+    Div,
+    /// Dividend (capital gain long term).
+    Divcgl,
+    /// Dividend (capital gain short term).
+    Divcgs,
+    /// Dividend fee.
+    Divfee,
+    /// Dividend adjusted (Foreign Tax Withheld).
+    Divft,
+    /// Dividend adjusted (NRA Withheld).
+    Divnra,
+    /// Dividend return of capital.
+    Divroc,
+    /// Dividend adjusted (Tefra Withheld).
+    Divtw,
+    /// Dividend (tax exempt).
+    Divtxex,
+    /// Fee denominated in USD.
+    Fee,
+    /// Interest (credit/margin).
+    Int,
+    /// Interest adjusted (NRA Withheld).
+    Intnra,
+    /// Interest adjusted (Tefra Withheld).
+    Inttw,
+    /// Journal entry.
+    Jnl,
+    /// Journal entry (cash).
+    Jnlc,
+    /// Journal entry (stock).
+    Jnls,
+    /// Merger/Acquisition.
+    Ma,
+    /// Name change.
+    Nc,
+    /// Option assignment.
+    Opasn,
+    /// Option expiration.
+    Opexp,
+    /// Option exercise.
+    Opxrc,
+    /// Pass Thru Charge.
+    Ptc,
+    /// Pass Thru Rebate.
+    Ptr,
+    /// Reorg CA.
+    Reorg,
+    /// Symbol change.
+    Sc,
+    /// Stock spinoff.
+    Sso,
+    /// Stock split.
+    Ssp,
+    /// An activity type that is not known to this library. It is only
+    /// ever decoded, and cannot be used in an `ActivityQuery`.
+    #[serde(other, skip_serializing)]
+    Other,
+}
+
+/// Whether a fill completed its order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillType {
+    /// The order was completely filled.
+    Fill,
+    /// The order was partially filled.
+    PartialFill,
+}
+
+/// An order fill.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TradeActivity {
+    /// An id for the activity. Can be used as a page token.
+    pub id: String,
+    /// Always `ActivityType::Fill`.
+    pub activity_type: ActivityType,
+    /// The time at which the execution occurred.
+    pub transaction_time: DateTime<Utc>,
+    /// Whether the fill completed the order.
+    #[serde(rename = "type")]
+    pub fill_type: FillType,
+    /// The per-share price that the trade was executed at.
+    #[serde(with = "super::serde::float")]
+    pub price: Float,
+    /// The number of shares involved in the trade execution.
+    #[serde(with = "super::serde::float")]
+    pub qty: Float,
+    /// Buy or sell.
+    pub side: Side,
+    /// The symbol of the security being traded.
+    pub symbol: String,
+    /// For partially filled orders, the quantity of shares that are left
+    /// to be filled.
+    #[serde(with = "super::serde::float")]
+    pub leaves_qty: Float,
+    /// The id for the order that filled.
+    pub order_id: Uuid,
+    /// The cumulative quantity of shares involved in the execution.
+    #[serde(with = "super::serde::float")]
+    pub cum_qty: Float,
+}
+
+/// Any activity other than a fill, such as dividends, fees and
+/// transfers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NonTradeActivity {
+    /// An id for the activity. Can be used as a page token.
+    pub id: String,
+    /// The kind of activity.
+    pub activity_type: ActivityType,
+    /// The date on which the activity occurred or on which the
+    /// transaction associated with the activity settled.
+    pub date: NaiveDate,
+    /// The net amount of money (positive or negative) associated with the
+    /// activity.
+    #[serde(with = "super::serde::float")]
+    pub net_amount: Float,
+    /// The symbol of the security involved with the activity. Not present
+    /// for all activity types.
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// For dividend activities, the number of shares that contributed to
+    /// the payment. Not present for other activity types.
+    #[serde(with = "super::serde::float_optional")]
+    #[serde(default)]
+    pub qty: Option<Float>,
+    /// For dividend activities, the average amount paid per share. Not
+    /// present for other activity types.
+    #[serde(with = "super::serde::float_optional")]
+    #[serde(default)]
+    pub per_share_amount: Option<Float>,
+    /// A description of the activity, when alpaca provides one.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// An account activity.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Activity {
+    /// An order fill.
+    Trade(TradeActivity),
+    /// Any other activity.
+    NonTrade(NonTradeActivity),
+}
+
+impl Activity {
+    /// The id of the activity.
+    pub fn id(&self) -> &str {
+        match self {
+            Activity::Trade(activity) => &activity.id,
+            Activity::NonTrade(activity) => &activity.id,
+        }
+    }
+
+    /// The kind of activity.
+    pub fn activity_type(&self) -> ActivityType {
+        match self {
+            Activity::Trade(activity) => activity.activity_type,
+            Activity::NonTrade(activity) => activity.activity_type,
+        }
+    }
+}
+
+/// Query parameters for listing account activities. `date` cannot be
+/// combined with `after` or `until`.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ActivityQuery {
+    /// Only activities of these types.
+    #[serde(serialize_with = "super::serde::comma_separated::serialize")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub activity_types: Vec<ActivityType>,
+    /// Only activities on this date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Only activities after this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<DateTime<Utc>>,
+    /// Only activities until this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    /// The chronological order of the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<SortDirection>,
+    /// The maximum number of activities per page. Alpaca defaults to 100,
+    /// which is also the largest page allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// Only activities after (or before, for descending order) the
+    /// activity with this id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
+}
+
+impl ActivityQuery {
+    /// The page size alpaca uses when none is given.
+    pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+    pub fn new() -> ActivityQuery {
+        ActivityQuery::default()
+    }
+
+    pub fn activity_types<I>(mut self, activity_types: I) -> Self
+    where
+        I: IntoIterator<Item = ActivityType>,
+    {
+        self.activity_types = activity_types.into_iter().collect();
+        self
+    }
+
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub fn after(mut self, after: DateTime<Utc>) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn direction(mut self, direction: SortDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn page_token<T>(mut self, page_token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.page_token = Some(page_token.into());
+        self
+    }
+}
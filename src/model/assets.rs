@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The class of an asset.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum AssetClass {
+    /// US equities.
+    UsEquity,
+    /// Crypto currencies.
+    Crypto,
+    /// An asset class that is not known to this library, holding the name
+    /// alpaca sent.
+    Other(String),
+}
+
+impl AssetClass {
+    /// The name alpaca uses for the asset class.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssetClass::UsEquity => "us_equity",
+            AssetClass::Crypto => "crypto",
+            AssetClass::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for AssetClass {
+    fn from(name: String) -> AssetClass {
+        match name.as_str() {
+            "us_equity" => AssetClass::UsEquity,
+            "crypto" => AssetClass::Crypto,
+            _ => AssetClass::Other(name),
+        }
+    }
+}
+
+impl From<AssetClass> for String {
+    fn from(class: AssetClass) -> String {
+        match class {
+            AssetClass::Other(name) => name,
+            class => class.as_str().to_string(),
+        }
+    }
+}
+
+/// Whether an asset is currently listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetStatus {
+    /// The asset is active.
+    Active,
+    /// The asset is inactive, for example because it was delisted.
+    Inactive,
+}
+
+/// The exchange an asset is listed on.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum Exchange {
+    /// NYSE American.
+    Amex,
+    /// NYSE Arca.
+    Arca,
+    /// Cboe BZX.
+    Bats,
+    /// New York Stock Exchange.
+    Nyse,
+    /// Nasdaq.
+    Nasdaq,
+    /// NYSE Arca, as reported for exchange traded products.
+    Nysearca,
+    /// Over the counter.
+    Otc,
+    /// An exchange that is not known to this library, holding the name
+    /// alpaca sent.
+    Other(String),
+}
+
+impl Exchange {
+    /// The name alpaca uses for the exchange.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Exchange::Amex => "AMEX",
+            Exchange::Arca => "ARCA",
+            Exchange::Bats => "BATS",
+            Exchange::Nyse => "NYSE",
+            Exchange::Nasdaq => "NASDAQ",
+            Exchange::Nysearca => "NYSEARCA",
+            Exchange::Otc => "OTC",
+            Exchange::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for Exchange {
+    fn from(name: String) -> Exchange {
+        match name.as_str() {
+            "AMEX" => Exchange::Amex,
+            "ARCA" => Exchange::Arca,
+            "BATS" => Exchange::Bats,
+            "NYSE" => Exchange::Nyse,
+            "NASDAQ" => Exchange::Nasdaq,
+            "NYSEARCA" => Exchange::Nysearca,
+            "OTC" => Exchange::Otc,
+            _ => Exchange::Other(name),
+        }
+    }
+}
+
+impl From<Exchange> for String {
+    fn from(exchange: Exchange) -> String {
+        match exchange {
+            Exchange::Other(name) => name,
+            exchange => exchange.as_str().to_string(),
+        }
+    }
+}
+
+/// An asset that may be traded on alpaca.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Asset {
+    /// Asset id.
+    pub id: Uuid,
+    /// Asset class.
+    pub class: AssetClass,
+    /// The exchange the asset is listed on.
+    pub exchange: Exchange,
+    /// Asset symbol.
+    pub symbol: String,
+    /// The official name of the asset.
+    #[serde(default)]
+    pub name: String,
+    /// Asset status.
+    pub status: AssetStatus,
+    /// The asset is tradable on alpaca or not.
+    pub tradable: bool,
+    /// The asset is marginable or not.
+    pub marginable: bool,
+    /// The asset is shortable or not.
+    pub shortable: bool,
+    /// The asset is easy-to-borrow or not (filtering for
+    /// `easy_to_borrow = true` is the best way to check whether the name
+    /// is currently available to short at alpaca).
+    pub easy_to_borrow: bool,
+    /// The asset is fractionable or not.
+    #[serde(default)]
+    pub fractionable: bool,
+}
+
+/// Query parameters for listing assets.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AssetQuery {
+    /// Only assets with this status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<AssetStatus>,
+    /// Only assets of this class.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_class: Option<AssetClass>,
+}
+
+impl AssetQuery {
+    pub fn new() -> AssetQuery {
+        AssetQuery::default()
+    }
+
+    pub fn status(mut self, status: AssetStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn asset_class(mut self, asset_class: AssetClass) -> Self {
+        self.asset_class = Some(asset_class);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn known_names_round_trip() {
+        let class: AssetClass = serde_json::from_value(json!("crypto")).unwrap();
+        assert_eq!(class, AssetClass::Crypto);
+        assert_eq!(serde_json::to_value(&class).unwrap(), json!("crypto"));
+
+        let exchange: Exchange = serde_json::from_value(json!("NYSEARCA")).unwrap();
+        assert_eq!(exchange, Exchange::Nysearca);
+        assert_eq!(serde_json::to_value(&exchange).unwrap(), json!("NYSEARCA"));
+    }
+
+    #[test]
+    fn unknown_names_are_kept() {
+        let class: AssetClass = serde_json::from_value(json!("us_option")).unwrap();
+        assert_eq!(class, AssetClass::Other("us_option".to_string()));
+        assert_eq!(class.as_str(), "us_option");
+        assert_eq!(serde_json::to_value(&class).unwrap(), json!("us_option"));
+
+        let exchange: Exchange = serde_json::from_value(json!("FTXU")).unwrap();
+        assert_eq!(exchange, Exchange::Other("FTXU".to_string()));
+        assert_eq!(serde_json::to_value(&exchange).unwrap(), json!("FTXU"));
+    }
+}
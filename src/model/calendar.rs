@@ -0,0 +1,37 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The market clock. Reports whether the market is open right now, and
+/// when it next opens and closes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Clock {
+    /// Current timestamp.
+    pub timestamp: DateTime<Utc>,
+    /// Whether or not the market is open.
+    pub is_open: bool,
+    /// Next market open timestamp.
+    pub next_open: DateTime<Utc>,
+    /// Next market close timestamp.
+    pub next_close: DateTime<Utc>,
+}
+
+/// A single trading day in the market calendar. Days on which the
+/// market is closed are not listed. All times are in US Eastern time.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CalendarDay {
+    /// Date the market is open.
+    pub date: NaiveDate,
+    /// The time the market opens at on this date. Usually 9:30am.
+    #[serde(with = "super::serde::time")]
+    pub open: NaiveTime,
+    /// The time the market closes at on this date. Usually 4:00pm, or
+    /// earlier on early-close days.
+    #[serde(with = "super::serde::time")]
+    pub close: NaiveTime,
+    /// The time the extended hours session opens at.
+    #[serde(with = "super::serde::time")]
+    pub session_open: NaiveTime,
+    /// The time the extended hours session closes at.
+    #[serde(with = "super::serde::time")]
+    pub session_close: NaiveTime,
+}
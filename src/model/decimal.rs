@@ -0,0 +1,126 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+pub use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+
+/// Values that can be used as a price or quantity in an order. This is
+/// implemented for `Decimal`, the primitive integer and float types, and
+/// strings containing a decimal number.
+///
+/// Floats are converted to the shortest decimal that round-trips, so
+/// `0.1_f64` becomes exactly `0.1`. Strings are parsed like the decimals
+/// received from alpaca, so scientific notation such as `"1e-3"` is
+/// accepted too.
+///
+/// The conversion fails for strings that are not a decimal number, and
+/// for floats that are infinite, `NaN`, or too large for a `Decimal`.
+/// The order builders do not fail right away, but report the value from
+/// `OrderRequest::validate`.
+pub trait IntoDecimal {
+    fn into_decimal(self) -> Result<Decimal, DecimalError>;
+}
+
+/// A value that could not be converted to a `Decimal`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecimalError {
+    input: String,
+}
+
+impl DecimalError {
+    fn new<T: ToString>(input: T) -> DecimalError {
+        DecimalError { input: input.to_string() }
+    }
+
+    /// The value that was given, as text.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a decimal number", self.input)
+    }
+}
+
+impl error::Error for DecimalError {}
+
+/// Parse a decimal number, in plain or scientific notation.
+pub(crate) fn parse_decimal(s: &str) -> Result<Decimal, rust_decimal::Error> {
+    Decimal::from_str(s).or_else(|_| Decimal::from_scientific(s))
+}
+
+impl IntoDecimal for Decimal {
+    fn into_decimal(self) -> Result<Decimal, DecimalError> {
+        Ok(self)
+    }
+}
+
+macro_rules! impl_into_decimal_int {
+    ($($t:ty),*) => {
+        $(
+            impl IntoDecimal for $t {
+                fn into_decimal(self) -> Result<Decimal, DecimalError> {
+                    Ok(Decimal::from(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_decimal_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl IntoDecimal for f32 {
+    fn into_decimal(self) -> Result<Decimal, DecimalError> {
+        Decimal::from_f32(self).ok_or_else(|| DecimalError::new(self))
+    }
+}
+
+impl IntoDecimal for f64 {
+    fn into_decimal(self) -> Result<Decimal, DecimalError> {
+        Decimal::from_f64(self).ok_or_else(|| DecimalError::new(self))
+    }
+}
+
+impl IntoDecimal for &str {
+    fn into_decimal(self) -> Result<Decimal, DecimalError> {
+        parse_decimal(self).map_err(|_| DecimalError::new(self))
+    }
+}
+
+impl IntoDecimal for String {
+    fn into_decimal(self) -> Result<Decimal, DecimalError> {
+        self.as_str().into_decimal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_numbers() {
+        assert_eq!(7u8.into_decimal(), Ok(Decimal::from(7)));
+        assert_eq!((-3i64).into_decimal(), Ok(Decimal::from(-3)));
+        assert_eq!(0.1f64.into_decimal(), Ok(Decimal::new(1, 1)));
+        assert_eq!(2.5f32.into_decimal(), Ok(Decimal::new(25, 1)));
+    }
+
+    #[test]
+    fn parses_strings_like_the_deserializer() {
+        assert_eq!("101.25".into_decimal(), Ok(Decimal::new(10125, 2)));
+        assert_eq!("1e-3".into_decimal(), Ok(Decimal::new(1, 3)));
+        assert_eq!(String::from("2.5E2").into_decimal(), Ok(Decimal::from(250)));
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_decimals() {
+        assert_eq!("abc".into_decimal().unwrap_err().input(), "abc");
+        assert_eq!(f64::NAN.into_decimal().unwrap_err().input(), "NaN");
+        assert_eq!(f64::INFINITY.into_decimal().unwrap_err().input(), "inf");
+        assert!(1e30f64.into_decimal().is_err());
+        assert!(f32::NEG_INFINITY.into_decimal().is_err());
+    }
+}
@@ -2,6 +2,35 @@ use std::error;
 use std::fmt;
 use serde::{Serialize, Deserialize};
 
+/// The classes of error that Alpaca reports. Alpaca error codes are eight
+/// digits long, and start with the HTTP status they were returned with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// The request was malformed or had invalid parameters (40010001).
+    InvalidRequest,
+    /// The api credentials are missing or wrong (40110000).
+    Unauthorized,
+    /// The account does not have enough buying power for the order.
+    InsufficientBuyingPower,
+    /// The account does not hold enough shares for the order.
+    InsufficientQty,
+    /// The order was denied by pattern day trading protection (40310100).
+    PatternDayTrading,
+    /// The request was denied for some other reason (403xxxxx).
+    Forbidden,
+    /// The requested order or resource does not exist (40410000).
+    NotFound,
+    /// The request was understood, but cannot be processed. For example,
+    /// canceling an order that has already been filled (42210000).
+    Unprocessable,
+    /// Too many requests were sent in a short period (42910000).
+    RateLimited,
+    /// Alpaca failed to process the request (500xxxxx).
+    InternalServerError,
+    /// A code that is not known to this library.
+    Other(i32),
+}
+
 /// todo: docs ...
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Error {
@@ -11,6 +40,41 @@ pub struct Error {
     message: String,
 }
 
+impl Error {
+    /// The raw error code returned by Alpaca.
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    /// The human readable message returned by Alpaca.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Classify the error code.
+    pub fn kind(&self) -> ErrorCode {
+        // Alpaca reuses 40310000 for several kinds of rejection, and only
+        // the message tells them apart.
+        let message = self.message.to_lowercase();
+
+        match self.code {
+            40010001 => ErrorCode::InvalidRequest,
+            40110000 => ErrorCode::Unauthorized,
+            40310000 if message.contains("buying power") =>
+                ErrorCode::InsufficientBuyingPower,
+            40310000 if message.contains("insufficient qty") =>
+                ErrorCode::InsufficientQty,
+            40310100 => ErrorCode::PatternDayTrading,
+            40300000..=40399999 => ErrorCode::Forbidden,
+            40410000 => ErrorCode::NotFound,
+            42210000 => ErrorCode::Unprocessable,
+            42910000 => ErrorCode::RateLimited,
+            50000000..=50099999 => ErrorCode::InternalServerError,
+            code => ErrorCode::Other(code),
+        }
+    }
+}
+
 impl fmt::Display for Error {
     /// todo: docs ...
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
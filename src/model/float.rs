@@ -0,0 +1,29 @@
+/// The number type used for market data, positions and other values that
+/// are reported by alpaca but not sent back to it. Order and account
+/// amounts use `Decimal` instead.
+///
+/// This is always `f64`. Enable the `rug` feature to convert values to
+/// the arbitrary-precision `rug::Float` with `ToRug`.
+pub type Float = f64;
+
+/// The precision, in bits, of the `rug::Float` values made by `ToRug`.
+/// This provides approximately 16 decimal places of precision, in line
+/// with the arbitrary-precision libraries that alpaca uses.
+#[cfg(feature = "rug")]
+pub const RUG_PRECISION: u32 = 53;
+
+/// Conversion to `rug::Float`, for arbitrary-precision arithmetic on the
+/// values reported by alpaca. Requires the `rug` feature, which needs the
+/// GMP and MPFR C libraries.
+#[cfg(feature = "rug")]
+pub trait ToRug {
+    /// Convert to a `rug::Float` with `RUG_PRECISION` bits of precision.
+    fn to_rug(&self) -> rug::Float;
+}
+
+#[cfg(feature = "rug")]
+impl ToRug for Float {
+    fn to_rug(&self) -> rug::Float {
+        rug::Float::with_val(RUG_PRECISION, *self)
+    }
+}
@@ -0,0 +1,263 @@
+use std::error;
+use std::fmt;
+use uuid::Uuid;
+
+use super::{Order, OrderStatus, TradeUpdate};
+
+impl OrderStatus {
+    /// Whether no further updates will occur for the order.
+    pub fn is_terminal(self) -> bool {
+        matches!(self,
+            OrderStatus::Filled
+            | OrderStatus::Canceled
+            | OrderStatus::Expired
+            | OrderStatus::Replaced
+            | OrderStatus::Rejected)
+    }
+
+    /// Whether the order is still live, and may yet be filled.
+    pub fn is_open(self) -> bool {
+        !self.is_terminal()
+    }
+
+    /// Whether a request to cancel the order can be accepted. Orders that
+    /// are already being canceled or replaced reject cancel requests.
+    pub fn is_cancelable(self) -> bool {
+        self.is_open()
+            && !matches!(self, OrderStatus::PendingCancel | OrderStatus::PendingReplace)
+    }
+
+    /// Whether a request to replace the order can be accepted. Besides
+    /// the states that reject cancel requests, orders that have not yet
+    /// been routed cannot be replaced.
+    pub fn is_replaceable(self) -> bool {
+        self.is_cancelable() && self != OrderStatus::PendingNew
+    }
+
+    /// Whether an order in this state can move to `next`. Terminal states
+    /// cannot be left, and an order cannot go back to being new once it
+    /// has been routed for execution. Repeating the current state is
+    /// always allowed, since partial fills repeat `PartiallyFilled`.
+    pub fn can_transition_to(self, next: OrderStatus) -> bool {
+        if self == next {
+            return true;
+        }
+        if self.is_terminal() {
+            return false;
+        }
+
+        match (self.stage(), next.stage()) {
+            (_, None) => true,
+            (Some(current), Some(next)) => current < next,
+            // A rejected cancel or replace request, or the start of the
+            // next trading day, puts the order back to `New`.
+            (None, Some(_)) =>
+                next == OrderStatus::New
+                    && matches!(self,
+                        OrderStatus::PendingCancel
+                        | OrderStatus::PendingReplace
+                        | OrderStatus::DoneForDay),
+        }
+    }
+
+    /// The order in which the states before execution are passed through,
+    /// or `None` for states reached once the order is live. Held legs
+    /// come first, since they are routed once the order they depend on
+    /// triggers them.
+    fn stage(self) -> Option<u8> {
+        match self {
+            OrderStatus::Held => Some(0),
+            OrderStatus::PendingNew => Some(1),
+            OrderStatus::Accepted => Some(2),
+            OrderStatus::AcceptedForBidding => Some(3),
+            OrderStatus::New => Some(4),
+            _ => None,
+        }
+    }
+}
+
+/// A reason `Order::apply_update` refused an update.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OrderUpdateError {
+    /// The update is for a different order.
+    WrongOrder {
+        /// Id of the tracked order.
+        expected: Uuid,
+        /// Id of the order in the update.
+        actual: Uuid,
+    },
+    /// The order cannot move between these states, see
+    /// `OrderStatus::can_transition_to`.
+    InvalidTransition {
+        /// Status of the tracked order.
+        from: OrderStatus,
+        /// Status reported by the update.
+        to: OrderStatus,
+    },
+}
+
+impl fmt::Display for OrderUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderUpdateError::WrongOrder { expected, actual } =>
+                write!(f, "update for order {} applied to order {}", actual, expected),
+            OrderUpdateError::InvalidTransition { from, to } =>
+                write!(f, "order cannot move from {:?} to {:?}", from, to),
+        }
+    }
+}
+
+impl error::Error for OrderUpdateError {}
+
+impl Order {
+    /// Bring a locally tracked order up to date with an update from the
+    /// `trade_updates` stream. The update is refused, and the order left
+    /// unchanged, if it belongs to another order or describes a state
+    /// change that cannot happen.
+    pub fn apply_update(&mut self, update: &TradeUpdate) -> Result<(), OrderUpdateError> {
+        if update.order.id != self.id {
+            return Err(OrderUpdateError::WrongOrder {
+                expected: self.id,
+                actual: update.order.id,
+            });
+        }
+
+        if !self.status.can_transition_to(update.order.status) {
+            return Err(OrderUpdateError::InvalidTransition {
+                from: self.status,
+                to: update.order.status,
+            });
+        }
+
+        *self = update.order.clone();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::orders;
+    use crate::model::TradeEvent;
+
+    use OrderStatus::*;
+
+    const ALL: [OrderStatus; 17] = [
+        New, PartiallyFilled, Filled, DoneForDay, Canceled, Expired, Replaced, PendingCancel,
+        PendingReplace, Accepted, PendingNew, AcceptedForBidding, Stopped, Rejected,
+        Suspended, Calculated, Held,
+    ];
+
+    #[test]
+    fn terminal_states() {
+        for &status in ALL.iter() {
+            let terminal = matches!(status, Filled | Canceled | Expired | Replaced | Rejected);
+            assert_eq!(status.is_terminal(), terminal, "{:?}", status);
+            assert_eq!(status.is_open(), !terminal, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn cancelable_and_replaceable() {
+        assert!(New.is_cancelable());
+        assert!(Held.is_cancelable());
+        assert!(PendingNew.is_cancelable());
+        assert!(!PendingCancel.is_cancelable());
+        assert!(!PendingReplace.is_cancelable());
+        assert!(!Filled.is_cancelable());
+
+        assert!(New.is_replaceable());
+        assert!(PartiallyFilled.is_replaceable());
+        assert!(!PendingNew.is_replaceable());
+        assert!(!PendingReplace.is_replaceable());
+        assert!(!Canceled.is_replaceable());
+    }
+
+    #[test]
+    fn allowed_transitions() {
+        let allowed = [
+            (Held, PendingNew),
+            (Held, Accepted),
+            (Held, New),
+            (Held, Canceled),
+            (PendingNew, Accepted),
+            (PendingNew, New),
+            (Accepted, New),
+            (AcceptedForBidding, New),
+            (New, PartiallyFilled),
+            (New, Filled),
+            (PartiallyFilled, PartiallyFilled),
+            (PartiallyFilled, Filled),
+            (New, PendingCancel),
+            (PendingCancel, Canceled),
+            (PendingCancel, New),
+            (PendingReplace, New),
+            (PendingReplace, Replaced),
+            (DoneForDay, New),
+            (New, Expired),
+            (PendingNew, Rejected),
+        ];
+
+        for &(from, to) in allowed.iter() {
+            assert!(from.can_transition_to(to), "{:?} -> {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn forbidden_transitions() {
+        let forbidden = [
+            (New, PendingNew),
+            (New, Accepted),
+            (New, Held),
+            (Accepted, PendingNew),
+            (PendingNew, Held),
+            (PartiallyFilled, New),
+            (Stopped, New),
+        ];
+
+        for &(from, to) in forbidden.iter() {
+            assert!(!from.can_transition_to(to), "{:?} -> {:?}", from, to);
+        }
+
+        // Terminal states cannot be left, but may be repeated.
+        for &from in ALL.iter().filter(|status| status.is_terminal()) {
+            for &to in ALL.iter() {
+                assert_eq!(from.can_transition_to(to), from == to, "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn events_of_states() {
+        assert_eq!(TradeEvent::from(Held), TradeEvent::PendingNew);
+        assert_eq!(TradeEvent::from(PartiallyFilled), TradeEvent::PartialFill);
+        assert_eq!(TradeEvent::from(Filled), TradeEvent::Fill);
+    }
+
+    fn order(id: &str, status: &str) -> Order {
+        orders::tests::order(serde_json::json!({ "id": id, "status": status }))
+    }
+
+    const ID: &str = "61e69015-8549-4bfd-b9c3-01e75843f47d";
+
+    #[test]
+    fn apply_update() {
+        let mut tracked = order(ID, "new");
+
+        let update = TradeUpdate::from_order(order(ID, "filled"));
+        assert_eq!(tracked.apply_update(&update), Ok(()));
+        assert_eq!(tracked.status, Filled);
+
+        let update = TradeUpdate::from_order(order(ID, "new"));
+        assert_eq!(tracked.apply_update(&update),
+                   Err(OrderUpdateError::InvalidTransition { from: Filled, to: New }));
+        assert_eq!(tracked.status, Filled);
+
+        let other = "904837e3-3b76-47ec-b432-046db621571b";
+        let update = TradeUpdate::from_order(order(other, "filled"));
+        assert_eq!(tracked.apply_update(&update), Err(OrderUpdateError::WrongOrder {
+            expected: ID.parse().unwrap(),
+            actual: other.parse().unwrap(),
+        }));
+    }
+}
@@ -0,0 +1,417 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::Float;
+
+/// A single trade reported by the market data api.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Trade {
+    /// Symbol. Historical queries report the symbol next to the trades
+    /// instead, and leave this empty until the client fills it in.
+    #[serde(rename = "S", default)]
+    pub symbol: String,
+    /// Trade id.
+    #[serde(rename = "i")]
+    pub id: u64,
+    /// Exchange code where the trade occurred.
+    #[serde(rename = "x")]
+    pub exchange: String,
+    /// Trade price.
+    #[serde(rename = "p", with = "super::serde::float")]
+    pub price: Float,
+    /// Trade size.
+    #[serde(rename = "s")]
+    pub size: u64,
+    /// Timestamp with nanosecond precision.
+    #[serde(rename = "t")]
+    pub timestamp: DateTime<Utc>,
+    /// Trade conditions.
+    #[serde(rename = "c", default)]
+    pub conditions: Vec<String>,
+    /// Tape.
+    #[serde(rename = "z", default)]
+    pub tape: String,
+}
+
+/// A single quote reported by the market data api.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Quote {
+    /// Symbol. Historical queries report the symbol next to the quotes
+    /// instead, and leave this empty until the client fills it in.
+    #[serde(rename = "S", default)]
+    pub symbol: String,
+    /// Ask exchange code.
+    #[serde(rename = "ax")]
+    pub ask_exchange: String,
+    /// Ask price.
+    #[serde(rename = "ap", with = "super::serde::float")]
+    pub ask_price: Float,
+    /// Ask size.
+    #[serde(rename = "as")]
+    pub ask_size: u64,
+    /// Bid exchange code.
+    #[serde(rename = "bx")]
+    pub bid_exchange: String,
+    /// Bid price.
+    #[serde(rename = "bp", with = "super::serde::float")]
+    pub bid_price: Float,
+    /// Bid size.
+    #[serde(rename = "bs")]
+    pub bid_size: u64,
+    /// Timestamp with nanosecond precision.
+    #[serde(rename = "t")]
+    pub timestamp: DateTime<Utc>,
+    /// Quote conditions.
+    #[serde(rename = "c", default)]
+    pub conditions: Vec<String>,
+    /// Tape.
+    #[serde(rename = "z", default)]
+    pub tape: String,
+}
+
+/// An aggregate of the trades in a time window.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Bar {
+    /// Symbol. Historical queries report the symbol next to the bars
+    /// instead, and leave this empty until the client fills it in.
+    #[serde(rename = "S", default)]
+    pub symbol: String,
+    /// Open price.
+    #[serde(rename = "o", with = "super::serde::float")]
+    pub open: Float,
+    /// High price.
+    #[serde(rename = "h", with = "super::serde::float")]
+    pub high: Float,
+    /// Low price.
+    #[serde(rename = "l", with = "super::serde::float")]
+    pub low: Float,
+    /// Close price.
+    #[serde(rename = "c", with = "super::serde::float")]
+    pub close: Float,
+    /// Volume.
+    #[serde(rename = "v")]
+    pub volume: u64,
+    /// Number of trades.
+    #[serde(rename = "n", default)]
+    pub trade_count: u64,
+    /// Volume weighted average price.
+    #[serde(rename = "vw", with = "super::serde::float_optional", default)]
+    pub vwap: Option<Float>,
+    /// Timestamp of the start of the window.
+    #[serde(rename = "t")]
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The latest market data of a symbol.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    /// The latest trade.
+    #[serde(default)]
+    pub latest_trade: Option<Trade>,
+    /// The latest quote, which holds the current NBBO.
+    #[serde(default)]
+    pub latest_quote: Option<Quote>,
+    /// The latest minute bar.
+    #[serde(default)]
+    pub minute_bar: Option<Bar>,
+    /// The bar of the current trading day.
+    #[serde(default)]
+    pub daily_bar: Option<Bar>,
+    /// The bar of the previous trading day.
+    #[serde(default)]
+    pub prev_daily_bar: Option<Bar>,
+}
+
+/// The source of market data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataFeed {
+    /// Investors Exchange. Available to every account.
+    Iex,
+    /// All US exchanges. Requires a market data subscription.
+    Sip,
+}
+
+impl DataFeed {
+    /// The name alpaca uses for the feed.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataFeed::Iex => "iex",
+            DataFeed::Sip => "sip",
+        }
+    }
+}
+
+/// The symbols to receive, per channel, on a market data stream.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Subscription {
+    /// Symbols to receive trades for. `"*"` subscribes to every symbol.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trades: Vec<String>,
+    /// Symbols to receive quotes for. `"*"` subscribes to every symbol.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quotes: Vec<String>,
+    /// Symbols to receive minute bars for. `"*"` subscribes to every
+    /// symbol.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bars: Vec<String>,
+}
+
+impl Subscription {
+    pub fn new() -> Subscription {
+        Subscription::default()
+    }
+
+    pub fn trades<I, T>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.trades = symbols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn quotes<I, T>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.quotes = symbols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn bars<I, T>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.bars = symbols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether no channel has any symbols.
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty() && self.quotes.is_empty() && self.bars.is_empty()
+    }
+}
+
+/// An event received on a market data stream.
+#[derive(Clone, Debug)]
+pub enum MarketData {
+    /// A trade.
+    Trade(Trade),
+    /// A quote.
+    Quote(Quote),
+    /// A minute bar.
+    Bar(Bar),
+    /// The symbols the stream is now subscribed to, sent after every
+    /// subscribe or unsubscribe.
+    Subscription(Subscription),
+}
+
+/// The time window aggregated by each bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeFrame {
+    /// 1 to 59 minutes.
+    Minutes(u32),
+    /// 1 to 23 hours.
+    Hours(u32),
+    /// One trading day.
+    Day,
+    /// One week.
+    Week,
+    /// 1, 2, 3, 4, 6 or 12 months.
+    Months(u32),
+}
+
+impl fmt::Display for TimeFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeFrame::Minutes(n) => write!(f, "{}Min", n),
+            TimeFrame::Hours(n) => write!(f, "{}Hour", n),
+            TimeFrame::Day => write!(f, "1Day"),
+            TimeFrame::Week => write!(f, "1Week"),
+            TimeFrame::Months(n) => write!(f, "{}Month", n),
+        }
+    }
+}
+
+impl Serialize for TimeFrame {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// How historical bars are adjusted for corporate actions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Adjustment {
+    /// No adjustment. This is the default.
+    Raw,
+    /// Adjusted for splits.
+    Split,
+    /// Adjusted for dividends.
+    Dividend,
+    /// Adjusted for splits and dividends.
+    All,
+}
+
+/// Query parameters for historical bars.
+#[derive(Clone, Debug, Serialize)]
+pub struct BarsQuery {
+    /// The symbols to fetch bars for.
+    #[serde(serialize_with = "super::serde::comma_separated::serialize")]
+    pub symbols: Vec<String>,
+    /// The time window of each bar.
+    pub timeframe: TimeFrame,
+    /// Only bars starting at or after this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    /// Only bars starting at or before this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    /// The number of bars fetched per page, up to 10000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Corporate action adjustment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adjustment: Option<Adjustment>,
+    /// The source of the data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed: Option<DataFeed>,
+}
+
+impl BarsQuery {
+    pub fn new<I, T>(symbols: I, timeframe: TimeFrame) -> BarsQuery
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        BarsQuery {
+            symbols: symbols.into_iter().map(Into::into).collect(),
+            timeframe,
+            start: None,
+            end: None,
+            limit: None,
+            adjustment: None,
+            feed: None,
+        }
+    }
+
+    pub fn start(mut self, start: DateTime<Utc>) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn end(mut self, end: DateTime<Utc>) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn adjustment(mut self, adjustment: Adjustment) -> Self {
+        self.adjustment = Some(adjustment);
+        self
+    }
+
+    pub fn feed(mut self, feed: DataFeed) -> Self {
+        self.feed = Some(feed);
+        self
+    }
+}
+
+/// Query parameters for historical trades and quotes.
+#[derive(Clone, Debug, Serialize)]
+pub struct DataQuery {
+    /// The symbols to fetch data for.
+    #[serde(serialize_with = "super::serde::comma_separated::serialize")]
+    pub symbols: Vec<String>,
+    /// Only data at or after this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    /// Only data at or before this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    /// The number of items fetched per page, up to 10000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// The source of the data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed: Option<DataFeed>,
+}
+
+impl DataQuery {
+    pub fn new<I, T>(symbols: I) -> DataQuery
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        DataQuery {
+            symbols: symbols.into_iter().map(Into::into).collect(),
+            start: None,
+            end: None,
+            limit: None,
+            feed: None,
+        }
+    }
+
+    pub fn start(mut self, start: DateTime<Utc>) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn end(mut self, end: DateTime<Utc>) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn feed(mut self, feed: DataFeed) -> Self {
+        self.feed = Some(feed);
+        self
+    }
+}
+
+/// A page of historical data, keyed by symbol.
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub(crate) struct DataPage<T> {
+    #[serde(alias = "bars", alias = "trades", alias = "quotes", default)]
+    pub items: Option<BTreeMap<String, Vec<T>>>,
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
+/// The response of the latest trade endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct LatestTrade {
+    pub symbol: String,
+    pub trade: Trade,
+}
+
+/// The response of the latest quote endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct LatestQuote {
+    pub symbol: String,
+    pub quote: Quote,
+}
+
+/// The response of the latest bar endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct LatestBar {
+    pub symbol: String,
+    pub bar: Bar,
+}
@@ -1,8 +1,37 @@
 mod account;
+mod activities;
+mod assets;
+mod calendar;
+mod decimal;
 mod error;
+mod float;
+mod lifecycle;
+mod market_data;
+mod multi_status;
 mod orders;
+mod portfolio;
+mod positions;
 mod serde;
+mod updates;
+mod validation;
+mod watchlists;
 
 pub use account::*;
+pub use activities::*;
+pub use assets::*;
+pub use calendar::*;
+pub use decimal::*;
+pub use float::*;
+pub use lifecycle::*;
+pub use market_data::*;
 pub use orders::*;
+pub use portfolio::*;
+pub use positions::*;
 pub use error::*;
+pub use updates::*;
+pub use validation::*;
+pub use watchlists::*;
+pub use multi_status::*;
+
+#[cfg(test)]
+pub(crate) use orders::tests::order as test_order;
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use super::Error;
+
+/// The body of a single entry in a multi-status (207) response. Alpaca
+/// answers bulk operations, such as canceling every open order, with one
+/// entry per affected object. Each entry holds either the object that was
+/// acted upon, or the error that prevented it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MultiStatusBody<T> {
+    /// The operation succeeded.
+    Ok(T),
+    /// The operation failed.
+    Err(Error),
+}
+
+impl<T> MultiStatusBody<T> {
+    /// Borrow the body as a `Result`.
+    pub fn as_result(&self) -> Result<&T, &Error> {
+        match self {
+            MultiStatusBody::Ok(value) => Ok(value),
+            MultiStatusBody::Err(error) => Err(error),
+        }
+    }
+
+    /// Convert the body into a `Result`.
+    pub fn into_result(self) -> Result<T, Error> {
+        match self {
+            MultiStatusBody::Ok(value) => Ok(value),
+            MultiStatusBody::Err(error) => Err(error),
+        }
+    }
+}
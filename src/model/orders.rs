@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
-use rug::{Assign, Float};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::validation::{convert, forget};
+use super::{AssetClass, Decimal, IntoDecimal, MultiStatusBody, OrderViolation};
+
 /// todo ...
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Side {
     /// todo ...
@@ -14,7 +16,7 @@ pub enum Side {
 }
 
 /// todo ...
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderType {
     /// todo ...
@@ -25,9 +27,12 @@ pub enum OrderType {
     Stop,
     /// todo ...
     StopLimit,
+    /// A stop order whose stop price follows the market by a fixed
+    /// amount or percentage, as set by `trail_price` or `trail_percent`.
+    TrailingStop,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TimeInForce {
     /// A day order is eligible for execution only on the day it is
@@ -72,7 +77,7 @@ pub enum TimeInForce {
     Fok,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderStatus {
     /// The order has been received by Alpaca, and routed to exchanges
@@ -128,12 +133,16 @@ pub enum OrderStatus {
     /// done for day), but remaining settlement calculations are still
     /// pending. This state only occurs on rare occasions.
     Calculated,
+    /// The order is a leg of a bracket, OCO or OTO order, and is waiting
+    /// for the order it depends on before it is routed for execution.
+    Held,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderClass {
     /// todo ...
+    #[serde(alias = "")]
     Simple,
     /// todo ...
     Bracket,
@@ -169,13 +178,19 @@ pub struct Order {
     /// Asset symbol.
     pub symbol: String,
     /// Asset class.
-    pub asset_class: String,
-    /// todo ...
-    #[serde(with = "super::serde::float")]
-    pub qty: Float,
-    /// todo ...
-    #[serde(with = "super::serde::float")]
-    pub filled_qty: Float,
+    pub asset_class: AssetClass,
+    /// The number of shares ordered. Not set for notional orders until
+    /// they are filled.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub qty: Option<Decimal>,
+    /// The dollar amount ordered, for notional orders.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub notional: Option<Decimal>,
+    /// todo ...
+    #[serde(with = "super::serde::decimal")]
+    pub filled_qty: Decimal,
     /// todo ...
     pub r#type: OrderType,
     /// todo ...
@@ -183,40 +198,278 @@ pub struct Order {
     // TIME IN FORCE
     pub time_in_force: TimeInForce,
     /// todo ...
-    #[serde(with = "super::serde::float_optional")]
-    pub limit_price: Option<Float>,
-    /// todo ...
-    #[serde(with = "super::serde::float_optional")]
-    pub stop_price: Option<Float>,
-    /// todo ...
-    #[serde(with = "super::serde::float_optional")]
-    pub filled_avg_price: Option<Float>,
+    #[serde(with = "super::serde::decimal_optional")]
+    pub limit_price: Option<Decimal>,
+    /// todo ...
+    #[serde(with = "super::serde::decimal_optional")]
+    pub stop_price: Option<Decimal>,
+    /// todo ...
+    #[serde(with = "super::serde::decimal_optional")]
+    pub filled_avg_price: Option<Decimal>,
+    /// The dollar amount a trailing stop trails the market by.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub trail_price: Option<Decimal>,
+    /// The percentage a trailing stop trails the market by.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub trail_percent: Option<Decimal>,
+    /// The high-water mark of a trailing stop: the highest price seen
+    /// since the order was submitted, or the lowest for sell orders. The
+    /// stop price follows it.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub hwm: Option<Decimal>,
     /// todo ...
     pub status: OrderStatus,
     /// todo ...
     pub extended_hours: bool,
+    /// The class of the order. Multi-leg orders are reported with the
+    /// class of the order they are part of.
+    #[serde(default)]
+    pub order_class: Option<OrderClass>,
     /// todo ...
     pub legs: Option<Vec<Order>>,
 }
 
+impl Order {
+    /// The legs of a multi-leg order. Empty unless the order was fetched
+    /// with its legs nested, see `OrderQuery::nested`.
+    pub fn leg_orders(&self) -> &[Order] {
+        self.legs.as_deref().unwrap_or(&[])
+    }
+
+    /// The take-profit limit order of a bracket, OCO or OTO order. The
+    /// take-profit of an OCO order is the order itself.
+    pub fn take_profit_leg(&self) -> Option<&Order> {
+        if self.order_class == Some(OrderClass::Oco) && self.r#type == OrderType::Limit {
+            return Some(self);
+        }
+        self.leg_orders().iter()
+            .find(|leg| leg.r#type == OrderType::Limit)
+    }
+
+    /// The stop or stop limit order of a bracket, OCO or OTO order.
+    pub fn stop_loss_leg(&self) -> Option<&Order> {
+        self.leg_orders().iter()
+            .find(|leg| matches!(leg.r#type, OrderType::Stop | OrderType::StopLimit))
+    }
+}
+
+/// Filters orders by status when listing them.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderQueryStatus {
+    /// Orders that can still be filled. This is the default.
+    Open,
+    /// Orders that are filled, canceled, expired or otherwise done.
+    Closed,
+    /// Every order.
+    All,
+}
+
+/// The chronological order in which results are returned.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    /// Oldest first.
+    Asc,
+    /// Newest first. This is the default.
+    Desc,
+}
+
+/// Query parameters for listing orders. By default, alpaca returns the
+/// 50 most recent open orders.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct OrderQuery {
+    /// Order status to be queried.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<OrderQueryStatus>,
+    /// The maximum number of orders in response. Alpaca defaults to 50
+    /// and allows at most 500.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Only orders submitted after this timestamp (exclusive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<DateTime<Utc>>,
+    /// Only orders submitted until this timestamp (exclusive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    /// The chronological order of the response, based on submission
+    /// time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<SortDirection>,
+    /// If true, multi-leg orders are rolled up under the `legs` field of
+    /// their primary order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nested: Option<bool>,
+    /// Only orders for these symbols.
+    #[serde(serialize_with = "super::serde::comma_separated::serialize")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+}
+
+impl OrderQuery {
+    /// The page size alpaca uses when no limit is given.
+    pub const DEFAULT_LIMIT: u32 = 50;
+
+    /// The largest page size alpaca accepts. Larger limits are capped to
+    /// this by alpaca.
+    pub const MAX_LIMIT: u32 = 500;
+
+    pub fn new() -> OrderQuery {
+        OrderQuery::default()
+    }
+
+    pub fn status(mut self, status: OrderQueryStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Return at most `limit` orders per page, capped to `MAX_LIMIT`.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(std::cmp::min(limit, Self::MAX_LIMIT));
+        self
+    }
+
+    pub fn after(mut self, after: DateTime<Utc>) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn direction(mut self, direction: SortDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn nested(mut self, nested: bool) -> Self {
+        self.nested = Some(nested);
+        self
+    }
+
+    pub fn symbols<I, T>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.symbols = symbols.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// The outcome of canceling a single order as part of
+/// `Client::cancel_all_orders`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderCancelation {
+    /// Id of the order that was to be canceled.
+    pub id: Uuid,
+    /// HTTP status of the cancel attempt.
+    pub status: u16,
+    /// The canceled order, or the reason the order could not be
+    /// canceled.
+    #[serde(default)]
+    pub body: Option<MultiStatusBody<Order>>,
+}
+
+impl OrderCancelation {
+    /// Whether the order was successfully canceled.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// The size of an order, either in shares or in dollars.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Amount {
+    /// A number of shares, which may be fractional.
+    Qty(#[serde(with = "super::serde::decimal")] Decimal),
+    /// A dollar amount. Alpaca buys or sells as many shares, including
+    /// fractions of a share, as the amount allows.
+    Notional(#[serde(with = "super::serde::decimal")] Decimal),
+}
+
+impl Amount {
+    /// Whether the order may trade a fraction of a share.
+    pub fn is_fractional(&self) -> bool {
+        match self {
+            Amount::Qty(qty) => !qty.fract().is_zero(),
+            Amount::Notional(_) => true,
+        }
+    }
+}
+
 /// todo ...
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct OrderTakeProfit {
     /// todo ...
-    #[serde(with = "super::serde::float")]
-    pub limit_price: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub limit_price: Decimal,
+    /// Values that could not be converted to decimals.
+    #[serde(skip)]
+    pub(crate) invalid: Vec<OrderViolation>,
 }
 
 /// todo ...
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct OrderStopLoss {
     /// todo ...
-    #[serde(with = "super::serde::float")]
-    pub stop_price: Float,
+    #[serde(with = "super::serde::decimal")]
+    pub stop_price: Decimal,
     /// todo ...
-    #[serde(with = "super::serde::float_optional")]
+    #[serde(with = "super::serde::decimal_optional")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit_price: Option<Float>,
+    pub limit_price: Option<Decimal>,
+    /// Values that could not be converted to decimals.
+    #[serde(skip)]
+    pub(crate) invalid: Vec<OrderViolation>,
+}
+
+impl OrderTakeProfit {
+    /// Take profit with a limit order at `limit_price`.
+    pub fn new<T>(limit_price: T) -> OrderTakeProfit
+    where
+        T: IntoDecimal,
+    {
+        let mut invalid = Vec::new();
+        let limit_price = convert(&mut invalid, "take_profit.limit_price", limit_price);
+
+        OrderTakeProfit {
+            limit_price: limit_price.unwrap_or_default(),
+            invalid,
+        }
+    }
+}
+
+impl OrderStopLoss {
+    /// Stop the loss with a stop order at `stop_price`.
+    pub fn new<T>(stop_price: T) -> OrderStopLoss
+    where
+        T: IntoDecimal,
+    {
+        let mut invalid = Vec::new();
+        let stop_price = convert(&mut invalid, "stop_loss.stop_price", stop_price);
+
+        OrderStopLoss {
+            stop_price: stop_price.unwrap_or_default(),
+            limit_price: None,
+            invalid,
+        }
+    }
+
+    /// Use a stop limit order with `limit_price`, instead of a stop order.
+    pub fn limit_price<T>(mut self, limit_price: T) -> Self
+    where
+        T: IntoDecimal,
+    {
+        self.limit_price = convert(&mut self.invalid, "stop_loss.limit_price", limit_price);
+        self
+    }
 }
 
 /// todo ...
@@ -224,9 +477,9 @@ pub struct OrderStopLoss {
 pub struct OrderRequest {
     /// Symbol or asset ID to identify the asset to trade.
     pub symbol: String,
-    /// The number of shares to trade.
-    #[serde(with = "super::serde::float")]
-    pub qty: Float,
+    /// The number of shares, or the dollar amount, to trade.
+    #[serde(flatten)]
+    pub amount: Amount,
     /// todo ...
     pub side: Side,
     /// todo ...
@@ -236,14 +489,26 @@ pub struct OrderRequest {
     pub time_in_force: TimeInForce,
     /// Limit price.  Required if `type` is `OrderType::Limit` or
     /// `OrderType::StopLimit`.
-    #[serde(with = "super::serde::float_optional")]
+    #[serde(with = "super::serde::decimal_optional")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit_price: Option<Float>,
+    pub limit_price: Option<Decimal>,
     /// Stop price.  Required if `type` is `OrderType::Stop` or
     /// `OrderType::StopLimit`.
-    #[serde(with = "super::serde::float_optional")]
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<Decimal>,
+    /// The dollar amount to trail the market by. Either this or
+    /// `trail_percent` is required if `type` is `OrderType::TrailingStop`.
+    #[serde(with = "super::serde::decimal_optional")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub stop_price: Option<Float>,
+    #[serde(default)]
+    pub trail_price: Option<Decimal>,
+    /// The percentage to trail the market by. Either this or
+    /// `trail_price` is required if `type` is `OrderType::TrailingStop`.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub trail_percent: Option<Decimal>,
     /// todo ...
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_hours: Option<bool>,
@@ -259,30 +524,37 @@ pub struct OrderRequest {
     /// todo ...
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_loss: Option<OrderStopLoss>,
+    /// Values that could not be converted to decimals, reported by
+    /// `validate`.
+    #[serde(skip)]
+    pub(crate) invalid: Vec<OrderViolation>,
 }
 
 impl OrderRequest {
     fn default() -> OrderRequest {
         OrderRequest {
             symbol: String::new(),
-            qty: Float::with_val(53, 0),
+            amount: Amount::Qty(Decimal::ZERO),
             side: Side::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::Day,
             limit_price: None,
             stop_price: None,
+            trail_price: None,
+            trail_percent: None,
             extended_hours: None,
             client_order_id: None,
             order_class: None,
             take_profit: None,
             stop_loss: None,
+            invalid: Vec::new(),
         }
     }
 
     pub fn buy<T, U>(symbol: T, qty: U) -> OrderRequest
     where
         T: Into<String>,
-        Float: rug::Assign<U>,
+        U: IntoDecimal,
     {
         OrderRequest::default()
             .side(Side::Buy)
@@ -293,7 +565,7 @@ impl OrderRequest {
     pub fn sell<T, U>(symbol: T, qty: U) -> OrderRequest
     where
         T: Into<String>,
-        Float: rug::Assign<U>,
+        U: IntoDecimal,
     {
         OrderRequest::default()
             .side(Side::Sell)
@@ -301,6 +573,30 @@ impl OrderRequest {
             .qty(qty)
     }
 
+    /// Buy `notional` dollars worth of `symbol`.
+    pub fn buy_notional<T, U>(symbol: T, notional: U) -> OrderRequest
+    where
+        T: Into<String>,
+        U: IntoDecimal,
+    {
+        OrderRequest::default()
+            .side(Side::Buy)
+            .symbol(symbol)
+            .notional(notional)
+    }
+
+    /// Sell `notional` dollars worth of `symbol`.
+    pub fn sell_notional<T, U>(symbol: T, notional: U) -> OrderRequest
+    where
+        T: Into<String>,
+        U: IntoDecimal,
+    {
+        OrderRequest::default()
+            .side(Side::Sell)
+            .symbol(symbol)
+            .notional(notional)
+    }
+
     pub fn symbol<T>(mut self, symbol: T) -> Self
     where
         T: Into<String>,
@@ -309,11 +605,25 @@ impl OrderRequest {
         self
     }
 
+    /// Trade `qty` shares, replacing any notional amount.
     pub fn qty<T>(mut self, qty: T) -> Self
     where
-        Float: rug::Assign<T>,
+        T: IntoDecimal,
+    {
+        forget(&mut self.invalid, "notional");
+        let qty = convert(&mut self.invalid, "qty", qty);
+        self.amount = Amount::Qty(qty.unwrap_or_default());
+        self
+    }
+
+    /// Trade `notional` dollars worth of shares, replacing any quantity.
+    pub fn notional<T>(mut self, notional: T) -> Self
+    where
+        T: IntoDecimal,
     {
-        self.qty.assign(qty);
+        forget(&mut self.invalid, "qty");
+        let notional = convert(&mut self.invalid, "notional", notional);
+        self.amount = Amount::Notional(notional.unwrap_or_default());
         self
     }
 
@@ -334,17 +644,38 @@ impl OrderRequest {
 
     pub fn limit_price<T>(mut self, limit_price: T) -> Self
     where
-        Float: rug::Assign<T>,
+        T: IntoDecimal,
     {
-        self.limit_price = Some(Float::with_val(53, limit_price));
+        self.limit_price = convert(&mut self.invalid, "limit_price", limit_price);
         self
     }
 
     pub fn stop_price<T>(mut self, stop_price: T) -> Self
     where
-        Float: rug::Assign<T>,
+        T: IntoDecimal,
     {
-        self.stop_price = Some(Float::with_val(53, stop_price));
+        self.stop_price = convert(&mut self.invalid, "stop_price", stop_price);
+        self
+    }
+
+    /// Trail the market by a dollar amount, replacing any trail
+    /// percentage.
+    pub fn trail_price<T>(mut self, trail_price: T) -> Self
+    where
+        T: IntoDecimal,
+    {
+        self.trail_price = convert(&mut self.invalid, "trail_price", trail_price);
+        self.trail_percent = None;
+        self
+    }
+
+    /// Trail the market by a percentage, replacing any trail price.
+    pub fn trail_percent<T>(mut self, trail_percent: T) -> Self
+    where
+        T: IntoDecimal,
+    {
+        self.trail_percent = convert(&mut self.invalid, "trail_percent", trail_percent);
+        self.trail_price = None;
         self
     }
 
@@ -363,43 +694,108 @@ impl OrderRequest {
         self
     }
 
-    pub fn take_profit(mut self, limit_price: Float) -> Self {
-        self.take_profit = Some(OrderTakeProfit { limit_price });
+    pub fn take_profit(mut self, take_profit: OrderTakeProfit) -> Self {
+        self.take_profit = Some(take_profit);
         self
     }
 
-    pub fn stop_loss(mut self, stop_price: Float, limit_price: Option<Float>) -> Self {
-        self.stop_loss = Some(OrderStopLoss {
-            stop_price,
-            limit_price,
-        });
+    pub fn stop_loss(mut self, stop_loss: OrderStopLoss) -> Self {
+        self.stop_loss = Some(stop_loss);
         self
     }
+
+    /// A bracket order: `entry`, followed by a take-profit and a stop loss
+    /// order that cancel each other once the entry fills. Fails if the
+    /// result would be rejected by `validate`, for example because the
+    /// take-profit is not above the stop loss of a buy order.
+    pub fn bracket(entry: OrderRequest, take_profit: OrderTakeProfit, stop_loss: OrderStopLoss)
+            -> Result<OrderRequest, Vec<OrderViolation>>
+    {
+        let mut order = entry;
+        order.order_class = Some(OrderClass::Bracket);
+        order.take_profit = Some(take_profit);
+        order.stop_loss = Some(stop_loss);
+        order.checked()
+    }
+
+    /// A one-cancels-other order: a take-profit and a stop loss order that
+    /// exit an existing position, where the first to fill cancels the
+    /// other. `exit` gives the side, symbol and size, and is turned into
+    /// the take-profit limit order.
+    pub fn oco(exit: OrderRequest, take_profit: OrderTakeProfit, stop_loss: OrderStopLoss)
+            -> Result<OrderRequest, Vec<OrderViolation>>
+    {
+        let mut order = exit;
+        order.order_class = Some(OrderClass::Oco);
+        order.order_type = OrderType::Limit;
+        order.limit_price = None;
+        order.take_profit = Some(take_profit);
+        order.stop_loss = Some(stop_loss);
+        order.checked()
+    }
+
+    /// A one-triggers-other order: `entry`, followed by a take-profit
+    /// order once it fills.
+    pub fn oto_take_profit(entry: OrderRequest, take_profit: OrderTakeProfit)
+            -> Result<OrderRequest, Vec<OrderViolation>>
+    {
+        let mut order = entry;
+        order.order_class = Some(OrderClass::Oto);
+        order.take_profit = Some(take_profit);
+        order.stop_loss = None;
+        order.checked()
+    }
+
+    /// A one-triggers-other order: `entry`, followed by a stop loss order
+    /// once it fills.
+    pub fn oto_stop_loss(entry: OrderRequest, stop_loss: OrderStopLoss)
+            -> Result<OrderRequest, Vec<OrderViolation>>
+    {
+        let mut order = entry;
+        order.order_class = Some(OrderClass::Oto);
+        order.take_profit = None;
+        order.stop_loss = Some(stop_loss);
+        order.checked()
+    }
+
+    fn checked(self) -> Result<OrderRequest, Vec<OrderViolation>> {
+        self.validate().map(|()| self)
+    }
 }
 
 /// todo ...
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct OrderReplace {
     /// todo ...
-    #[serde(with = "super::serde::float_optional")]
+    #[serde(with = "super::serde::decimal_optional")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub qty: Option<Float>,
+    pub qty: Option<Decimal>,
     /// todo ...
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_in_force: Option<TimeInForce>,
     /// Limit price.  Required if `type` is `OrderType::Limit` or
     /// `OrderType::StopLimit`.
-    #[serde(with = "super::serde::float_optional")]
+    #[serde(with = "super::serde::decimal_optional")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit_price: Option<Float>,
+    pub limit_price: Option<Decimal>,
     /// Stop price.  Required if `type` is `OrderType::Stop` or
     /// `OrderType::StopLimit`.
-    #[serde(with = "super::serde::float_optional")]
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<Decimal>,
+    /// The new trail of a trailing stop order. This is a dollar amount
+    /// or a percentage, matching how the order was submitted.
+    #[serde(with = "super::serde::decimal_optional")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub stop_price: Option<Float>,
+    #[serde(default)]
+    pub trail: Option<Decimal>,
     /// todo ...
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_order_id: Option<String>,
+    /// Values that could not be converted to decimals, reported by
+    /// `validate`.
+    #[serde(skip)]
+    pub(crate) invalid: Vec<OrderViolation>,
 }
 
 impl OrderReplace {
@@ -409,15 +805,17 @@ impl OrderReplace {
             time_in_force: None,
             limit_price: None,
             stop_price: None,
-            client_order_id: None
+            trail: None,
+            client_order_id: None,
+            invalid: Vec::new(),
         }
     }
 
     pub fn qty<T>(mut self, qty: T) -> Self
     where
-        Float: rug::Assign<T>,
+        T: IntoDecimal,
     {
-        self.qty = Some(Float::with_val(53, qty));
+        self.qty = convert(&mut self.invalid, "qty", qty);
         self
     }
 
@@ -428,17 +826,37 @@ impl OrderReplace {
 
     pub fn limit_price<T>(mut self, limit_price: T) -> Self
     where
-        Float: rug::Assign<T>,
+        T: IntoDecimal,
     {
-        self.limit_price = Some(Float::with_val(53, limit_price));
+        self.limit_price = convert(&mut self.invalid, "limit_price", limit_price);
         self
     }
 
     pub fn stop_price<T>(mut self, stop_price: T) -> Self
     where
-        Float: rug::Assign<T>,
+        T: IntoDecimal,
     {
-        self.stop_price = Some(Float::with_val(53, stop_price));
+        self.stop_price = convert(&mut self.invalid, "stop_price", stop_price);
+        self
+    }
+
+    /// Change the trail of a trailing stop order submitted with
+    /// `OrderRequest::trail_price`.
+    pub fn trail_price<T>(mut self, trail_price: T) -> Self
+    where
+        T: IntoDecimal,
+    {
+        self.trail = convert(&mut self.invalid, "trail", trail_price);
+        self
+    }
+
+    /// Change the trail of a trailing stop order submitted with
+    /// `OrderRequest::trail_percent`.
+    pub fn trail_percent<T>(mut self, trail_percent: T) -> Self
+    where
+        T: IntoDecimal,
+    {
+        self.trail = convert(&mut self.invalid, "trail", trail_percent);
         self
     }
 
@@ -451,3 +869,151 @@ impl OrderReplace {
     }
 }
 
+impl Default for OrderReplace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// An order as sent by alpaca, with `fields` replaced.
+    pub(crate) fn order(fields: Value) -> Order {
+        let mut order = json!({
+            "id": "61e69015-8549-4bfd-b9c3-01e75843f47d",
+            "client_order_id": "eb9e2aaa-f71a-4f51-b5b4-52a6c565dad4",
+            "created_at": "2021-03-16T18:38:01.942282Z",
+            "updated_at": "2021-03-16T18:38:01.942282Z",
+            "submitted_at": "2021-03-16T18:38:01.937734Z",
+            "filled_at": null,
+            "expired_at": null,
+            "canceled_at": null,
+            "failed_at": null,
+            "replaced_at": null,
+            "replaced_by": null,
+            "replaces": null,
+            "asset_id": "b0b6dd9d-8b9b-48a9-ba46-b9d54906e415",
+            "symbol": "AAPL",
+            "asset_class": "us_equity",
+            "notional": null,
+            "qty": "1",
+            "filled_qty": "0",
+            "filled_avg_price": null,
+            "order_class": "",
+            "order_type": "market",
+            "type": "market",
+            "side": "buy",
+            "time_in_force": "day",
+            "limit_price": null,
+            "stop_price": null,
+            "status": "new",
+            "extended_hours": false,
+            "legs": null,
+            "trail_percent": null,
+            "trail_price": null,
+            "hwm": null
+        });
+
+        for (key, value) in fields.as_object().unwrap() {
+            order[key] = value.clone();
+        }
+
+        serde_json::from_value(order).unwrap()
+    }
+
+    #[test]
+    fn decode_order() {
+        let order = order(json!({ "limit_price": "101.25", "type": "limit" }));
+        assert_eq!(order.qty, Some(Decimal::from(1)));
+        assert_eq!(order.notional, None);
+        assert_eq!(order.limit_price, Some(Decimal::new(10125, 2)));
+        assert_eq!(order.order_class, Some(OrderClass::Simple));
+        assert_eq!(order.asset_class, AssetClass::UsEquity);
+    }
+
+    #[test]
+    fn decode_notional_order() {
+        let order = order(json!({ "notional": "500", "qty": null, "status": "accepted" }));
+        assert_eq!(order.qty, None);
+        assert_eq!(order.notional, Some(Decimal::from(500)));
+        assert_eq!(order.status, OrderStatus::Accepted);
+    }
+
+    #[test]
+    fn decode_bracket_order_with_legs() {
+        let leg = |id: &str, order_type: &str, price: Value| json!({
+            "id": id,
+            "client_order_id": "leg",
+            "created_at": "2021-03-16T18:38:01.942282Z",
+            "updated_at": null,
+            "submitted_at": "2021-03-16T18:38:01.937734Z",
+            "filled_at": null,
+            "expired_at": null,
+            "canceled_at": null,
+            "failed_at": null,
+            "asset_id": "b0b6dd9d-8b9b-48a9-ba46-b9d54906e415",
+            "symbol": "AAPL",
+            "asset_class": "us_equity",
+            "qty": "1",
+            "filled_qty": "0",
+            "filled_avg_price": null,
+            "order_class": "bracket",
+            "type": order_type,
+            "side": "sell",
+            "time_in_force": "gtc",
+            "limit_price": price["limit_price"],
+            "stop_price": price["stop_price"],
+            "status": "held",
+            "extended_hours": false,
+            "legs": null
+        });
+
+        let order = order(json!({
+            "order_class": "bracket",
+            "type": "limit",
+            "limit_price": "100",
+            "time_in_force": "gtc",
+            "legs": [
+                leg("1e0cbaef-a2b4-4a1f-9bda-8a7e4a1f7b01", "limit",
+                    json!({ "limit_price": "110", "stop_price": null })),
+                leg("1e0cbaef-a2b4-4a1f-9bda-8a7e4a1f7b02", "stop",
+                    json!({ "limit_price": null, "stop_price": "90" })),
+            ],
+        }));
+
+        assert_eq!(order.order_class, Some(OrderClass::Bracket));
+        assert_eq!(order.leg_orders().len(), 2);
+
+        let take_profit = order.take_profit_leg().unwrap();
+        assert_eq!(take_profit.limit_price, Some(Decimal::from(110)));
+        assert_eq!(take_profit.status, OrderStatus::Held);
+        assert_eq!(take_profit.side, Side::Sell);
+
+        let stop_loss = order.stop_loss_leg().unwrap();
+        assert_eq!(stop_loss.stop_price, Some(Decimal::from(90)));
+    }
+
+    #[test]
+    fn oco_take_profit_is_the_order_itself() {
+        let order = order(json!({ "order_class": "oco", "type": "limit", "limit_price": "110" }));
+        assert_eq!(order.take_profit_leg().map(|leg| leg.id), Some(order.id));
+        assert!(order.stop_loss_leg().is_none());
+    }
+
+    #[test]
+    fn encode_order_request() {
+        let request = serde_json::to_value(OrderRequest::buy("AAPL", "0.37")).unwrap();
+        assert_eq!(request["qty"], "0.37");
+        assert!(request.get("notional").is_none());
+
+        let request = serde_json::to_value(OrderRequest::sell_notional("AAPL", 250)).unwrap();
+        assert_eq!(request["notional"], "250");
+        assert_eq!(request["side"], "sell");
+        assert!(request.get("qty").is_none());
+    }
+}
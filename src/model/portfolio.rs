@@ -0,0 +1,154 @@
+use std::fmt;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::Float;
+
+/// The length of time covered by a portfolio history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Period {
+    /// A number of days.
+    Days(u32),
+    /// A number of weeks.
+    Weeks(u32),
+    /// A number of months.
+    Months(u32),
+    /// A number of years.
+    Years(u32),
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Days(n) => write!(f, "{}D", n),
+            Period::Weeks(n) => write!(f, "{}W", n),
+            Period::Months(n) => write!(f, "{}M", n),
+            Period::Years(n) => write!(f, "{}A", n),
+        }
+    }
+}
+
+impl Serialize for Period {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// The resolution of a portfolio history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HistoryTimeFrame {
+    #[serde(rename = "1Min")]
+    OneMinute,
+    #[serde(rename = "5Min")]
+    FiveMinutes,
+    #[serde(rename = "15Min")]
+    FifteenMinutes,
+    #[serde(rename = "1H")]
+    OneHour,
+    #[serde(rename = "1D")]
+    OneDay,
+}
+
+/// Query parameters for the portfolio history. Alpaca picks a period
+/// and timeframe based on the account when they are left out.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PortfolioHistoryQuery {
+    /// The length of time covered, ending at `date_end`. Defaults to one
+    /// month.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+    /// The resolution of the history. Resolutions under a day can only
+    /// cover up to 30 days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<HistoryTimeFrame>,
+    /// The last day covered. Defaults to the current market date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_end: Option<NaiveDate>,
+    /// Whether to include extended hours in the result. Only applies to
+    /// resolutions under a day.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_hours: Option<bool>,
+}
+
+impl PortfolioHistoryQuery {
+    pub fn new() -> PortfolioHistoryQuery {
+        PortfolioHistoryQuery::default()
+    }
+
+    pub fn period(mut self, period: Period) -> Self {
+        self.period = Some(period);
+        self
+    }
+
+    pub fn timeframe(mut self, timeframe: HistoryTimeFrame) -> Self {
+        self.timeframe = Some(timeframe);
+        self
+    }
+
+    pub fn date_end(mut self, date_end: NaiveDate) -> Self {
+        self.date_end = Some(date_end);
+        self
+    }
+
+    pub fn extended_hours(mut self, extended_hours: bool) -> Self {
+        self.extended_hours = Some(extended_hours);
+        self
+    }
+}
+
+/// The value of the account over time. Values are reported as parallel
+/// lists, one entry per timestamp; use `points` to walk them together.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PortfolioHistory {
+    /// The time of each entry.
+    #[serde(rename = "timestamp", with = "super::serde::timestamps")]
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// Equity value of the account at each timestamp. Missing for times
+    /// at which the account had no value, such as before it was opened.
+    #[serde(with = "super::serde::float_optional_vec")]
+    pub equity: Vec<Option<Float>>,
+    /// Profit and loss in dollars, relative to `base_value`.
+    #[serde(with = "super::serde::float_optional_vec")]
+    pub profit_loss: Vec<Option<Float>>,
+    /// Profit and loss as a ratio of `base_value`.
+    #[serde(with = "super::serde::float_optional_vec")]
+    pub profit_loss_pct: Vec<Option<Float>>,
+    /// The equity the profit and loss are measured against.
+    #[serde(with = "super::serde::float_optional", default)]
+    pub base_value: Option<Float>,
+    /// The resolution of the history.
+    pub timeframe: HistoryTimeFrame,
+}
+
+/// The values of a portfolio history at one point in time.
+#[derive(Clone, Copy, Debug)]
+pub struct PortfolioPoint<'a> {
+    /// The time of the entry.
+    pub timestamp: DateTime<Utc>,
+    /// Equity value of the account.
+    pub equity: Option<&'a Float>,
+    /// Profit and loss in dollars.
+    pub profit_loss: Option<&'a Float>,
+    /// Profit and loss as a ratio of the base value.
+    pub profit_loss_pct: Option<&'a Float>,
+}
+
+impl PortfolioHistory {
+    /// Walk the history one timestamp at a time.
+    pub fn points(&self) -> impl Iterator<Item = PortfolioPoint<'_>> {
+        fn at(v: &[Option<Float>], i: usize) -> Option<&Float> {
+            v.get(i).and_then(Option::as_ref)
+        }
+
+        self.timestamps.iter().enumerate().map(move |(i, timestamp)| {
+            PortfolioPoint {
+                timestamp: *timestamp,
+                equity: at(&self.equity, i),
+                profit_loss: at(&self.profit_loss, i),
+                profit_loss_pct: at(&self.profit_loss_pct, i),
+            }
+        })
+    }
+}
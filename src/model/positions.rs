@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::validation::convert;
+use super::{
+    AssetClass, Decimal, Exchange, Float, IntoDecimal, MultiStatusBody, Order, OrderViolation,
+};
+
+/// Whether a position is long or short.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionSide {
+    /// The position was opened by buying.
+    Long,
+    /// The position was opened by selling short.
+    Short,
+}
+
+/// An open position in a single asset. Prices and values are updated
+/// live while the market is open.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Position {
+    /// Asset id.
+    pub asset_id: Uuid,
+    /// Asset symbol.
+    pub symbol: String,
+    /// Exchange of the asset.
+    pub exchange: Exchange,
+    /// Asset class.
+    pub asset_class: AssetClass,
+    /// Average entry price of the position.
+    #[serde(with = "super::serde::float")]
+    pub avg_entry_price: Float,
+    /// The number of shares.
+    #[serde(with = "super::serde::float")]
+    pub qty: Float,
+    /// Long or short position.
+    pub side: PositionSide,
+    /// Total dollar amount of the position.
+    #[serde(with = "super::serde::float")]
+    pub market_value: Float,
+    /// Total cost basis in dollars.
+    #[serde(with = "super::serde::float")]
+    pub cost_basis: Float,
+    /// Unrealized profit/loss in dollars.
+    #[serde(with = "super::serde::float")]
+    pub unrealized_pl: Float,
+    /// Unrealized profit/loss percent (by a factor of 1).
+    #[serde(with = "super::serde::float")]
+    pub unrealized_plpc: Float,
+    /// Unrealized profit/loss in dollars for the day.
+    #[serde(with = "super::serde::float")]
+    pub unrealized_intraday_pl: Float,
+    /// Unrealized profit/loss percent for the day (by a factor of 1).
+    #[serde(with = "super::serde::float")]
+    pub unrealized_intraday_plpc: Float,
+    /// Current asset price per share.
+    #[serde(with = "super::serde::float")]
+    pub current_price: Float,
+    /// Last day's asset price per share based on the closing value of
+    /// the last trading day.
+    #[serde(with = "super::serde::float")]
+    pub lastday_price: Float,
+    /// Percent change from last day price (by a factor of 1).
+    #[serde(with = "super::serde::float")]
+    pub change_today: Float,
+}
+
+/// Parameters for closing a position. By default, the entire position
+/// is liquidated.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ClosePosition {
+    /// The number of shares to liquidate.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<Decimal>,
+    /// The percentage of the position to liquidate, between 0 and 100.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<Decimal>,
+    /// Values that could not be converted to decimals, reported by
+    /// `validate`.
+    #[serde(skip)]
+    pub(crate) invalid: Vec<OrderViolation>,
+}
+
+impl ClosePosition {
+    /// Liquidate the entire position.
+    pub fn all() -> ClosePosition {
+        ClosePosition::default()
+    }
+
+    /// Liquidate `qty` shares of the position.
+    pub fn qty<T>(qty: T) -> ClosePosition
+    where
+        T: IntoDecimal,
+    {
+        let mut invalid = Vec::new();
+        let qty = convert(&mut invalid, "qty", qty);
+
+        ClosePosition {
+            qty,
+            percentage: None,
+            invalid,
+        }
+    }
+
+    /// Liquidate `percentage` percent of the position.
+    pub fn percentage<T>(percentage: T) -> ClosePosition
+    where
+        T: IntoDecimal,
+    {
+        let mut invalid = Vec::new();
+        let percentage = convert(&mut invalid, "percentage", percentage);
+
+        ClosePosition {
+            qty: None,
+            percentage,
+            invalid,
+        }
+    }
+}
+
+/// The outcome of closing a single position as part of
+/// `Client::close_all_positions`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PositionClosure {
+    /// Symbol of the position that was to be closed.
+    pub symbol: String,
+    /// HTTP status of the close attempt.
+    pub status: u16,
+    /// The order placed to close the position, or the reason the position
+    /// could not be closed.
+    #[serde(default)]
+    pub body: Option<MultiStatusBody<Order>>,
+}
+
+impl PositionClosure {
+    /// Whether the closing order was successfully placed.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
@@ -0,0 +1,52 @@
+use serde::ser::Error;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// Serialize a list of values as a single comma separated string, which
+/// is how alpaca expects lists in query parameters. Each value is written
+/// the way it serializes on its own, so enums keep their serde renames.
+pub fn serialize<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer,
+          T: Serialize,
+{
+    let mut parts = Vec::with_capacity(values.len());
+    for value in values {
+        match serde_json::to_value(value).map_err(S::Error::custom)? {
+            Value::String(part) => parts.push(part),
+            other => return Err(S::Error::custom(
+                format!("{} cannot be part of a comma separated list", other))),
+        }
+    }
+
+    serializer.serialize_str(&parts.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use crate::model::ActivityType;
+
+    #[derive(Serialize)]
+    struct Query<T: Serialize> {
+        #[serde(serialize_with = "super::serialize")]
+        values: Vec<T>,
+    }
+
+    fn encode<T: Serialize>(values: Vec<T>) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(Query { values }).map(|query| query["values"].clone())
+    }
+
+    #[test]
+    fn joins_values() {
+        assert_eq!(encode(vec!["AAPL", "MSFT"]).unwrap(), "AAPL,MSFT");
+        assert_eq!(encode(Vec::<String>::new()).unwrap(), "");
+    }
+
+    #[test]
+    fn uses_serde_names() {
+        let types = vec![ActivityType::Fill, ActivityType::Divtxex];
+        assert_eq!(encode(types).unwrap(), "FILL,DIVTXEX");
+        assert!(encode(vec![ActivityType::Other]).is_err());
+    }
+}
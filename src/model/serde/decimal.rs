@@ -0,0 +1,105 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+
+use super::super::parse_decimal;
+
+/// Serialize a decimal as a string, keeping its scale, so that values
+/// are sent back to alpaca exactly as they were received.
+pub fn serialize<S>(d: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    serializer.collect_str(d)
+}
+
+/// Deserialize a decimal from either a string or a json number. Strings
+/// are parsed exactly.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+    where D: Deserializer<'de>
+{
+    deserializer.deserialize_any(DecimalVisitor)
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a number, or a string containing a number")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Decimal, E>
+        where E: de::Error
+    {
+        parse_decimal(s).map_err(E::custom)
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Decimal, E>
+        where E: de::Error
+    {
+        Decimal::from_f64(v)
+            .ok_or_else(|| E::custom(format!("{} cannot be represented as a decimal", v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Amount {
+        #[serde(with = "super")]
+        value: Decimal,
+        #[serde(with = "crate::model::serde::decimal_optional")]
+        optional: Option<Decimal>,
+    }
+
+    fn decode(value: serde_json::Value) -> Result<Amount, serde_json::Error> {
+        serde_json::from_value(json!({ "value": value, "optional": null }))
+    }
+
+    #[test]
+    fn decodes_strings_exactly() {
+        assert_eq!(decode(json!("0.1")).unwrap().value, Decimal::new(1, 1));
+        assert_eq!(decode(json!("101.2500")).unwrap().value.to_string(), "101.2500");
+        assert_eq!(decode(json!("1e-3")).unwrap().value, Decimal::new(1, 3));
+        assert!(decode(json!("abc")).is_err());
+    }
+
+    #[test]
+    fn decodes_numbers() {
+        assert_eq!(decode(json!(42)).unwrap().value, Decimal::from(42));
+        assert_eq!(decode(json!(-7)).unwrap().value, Decimal::from(-7));
+        assert_eq!(decode(json!(0.25)).unwrap().value, Decimal::new(25, 2));
+    }
+
+    #[test]
+    fn round_trips_as_strings() {
+        let amount: Amount = serde_json::from_value(json!({
+            "value": "10.50",
+            "optional": "0.30",
+        })).unwrap();
+
+        assert_eq!(amount.optional, Some(Decimal::new(30, 2)));
+        assert_eq!(serde_json::to_value(&amount).unwrap(),
+                   json!({ "value": "10.50", "optional": "0.30" }));
+
+        let amount = decode(json!("1")).unwrap();
+        assert_eq!(amount.optional, None);
+        assert_eq!(serde_json::to_value(&amount).unwrap(),
+                   json!({ "value": "1", "optional": null }));
+    }
+}
@@ -0,0 +1,38 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+struct Wrapper(Decimal);
+
+impl Serialize for Wrapper {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        super::decimal::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Wrapper {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        Ok(Wrapper(super::decimal::deserialize(deserializer)?))
+    }
+}
+
+/// Serialize an optional decimal as a string, or `null`.
+pub fn serialize<S>(d: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    match d {
+        Some(d) => serializer.serialize_some(&Wrapper(*d)),
+        None    => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize an optional decimal from a string, a json number, or
+/// `null`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+    where D: Deserializer<'de>
+{
+    Ok(Option::deserialize(deserializer)?.map(|Wrapper(d)| d))
+}
@@ -1,27 +1,49 @@
-use rug::Float;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serialize, Serializer};
 
-/// todo ...
+use super::super::Float;
+
+/// Serialize a float as a string, the way the trading api sends them.
 pub fn serialize<S>(f: &Float, serializer: S) -> Result<S::Ok, S::Error>
     where S: Serializer
 {
-    f.to_string_radix(10, None).serialize(serializer)
+    f.to_string().serialize(serializer)
 }
 
-/// todo ...
+/// Deserialize a float from either a string or a json number. The trading
+/// api sends numbers as strings, while the market data api sends plain
+/// json numbers.
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Float, D::Error>
     where D: Deserializer<'de>
 {
-    use serde::de::Error;
-
-    // We use 53 bits of precision here. This provides us with
-    // approximately 16 decimal places of precision. This is in line with
-    // some of the other arbitrary-precision libraries that alpaca uses. I
-    // personally wish that alpaca went with a fixed width decimal instead.
-    // See https://github.com/shopspring/decimal.
-    let precision = 53;
-
-    let s = String::deserialize(deserializer)?;
-    let partial = Float::parse(s).map_err(Error::custom)?;
-    Ok(Float::with_val(precision, partial))
+    deserializer.deserialize_any(FloatVisitor)
+}
+
+struct FloatVisitor;
+
+impl<'de> Visitor<'de> for FloatVisitor {
+    type Value = Float;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a number, or a string containing a number")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Float, E>
+        where E: de::Error
+    {
+        s.parse().map_err(E::custom)
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Float, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Float, E> {
+        Ok(v as Float)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Float, E> {
+        Ok(v as Float)
+    }
 }
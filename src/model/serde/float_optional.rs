@@ -1,7 +1,8 @@
 use ref_cast::RefCast;
-use rug::Float;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use super::super::Float;
+
 #[derive(RefCast)]
 #[repr(transparent)]
 struct Wrapper(Float);
@@ -0,0 +1,39 @@
+use ref_cast::RefCast;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::super::Float;
+
+#[derive(RefCast)]
+#[repr(transparent)]
+struct Wrapper(Option<Float>);
+
+impl Serialize for Wrapper {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        super::float_optional::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Wrapper {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        Ok(Wrapper(super::float_optional::deserialize(deserializer)?))
+    }
+}
+
+/// Serialize a list of floats, writing missing values as `null`.
+pub fn serialize<S>(v: &[Option<Float>], serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    serializer.collect_seq(v.iter().map(Wrapper::ref_cast))
+}
+
+/// Deserialize a list of floats, any of which may be `null`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Option<Float>>, D::Error>
+    where D: Deserializer<'de>
+{
+    let v = Vec::<Wrapper>::deserialize(deserializer)?;
+    Ok(v.into_iter().map(|Wrapper(f)| f).collect())
+}
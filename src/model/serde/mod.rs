@@ -1,2 +1,8 @@
+pub mod comma_separated;
+pub mod decimal;
+pub mod decimal_optional;
 pub mod float;
 pub mod float_optional;
+pub mod float_optional_vec;
+pub mod time;
+pub mod timestamps;
@@ -0,0 +1,54 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialize a time of day as `HH:MM`.
+pub fn serialize<S>(t: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    t.format("%H:%M").to_string().serialize(serializer)
+}
+
+/// Deserialize a time of day. Alpaca writes these either as `HH:MM`
+/// or, for extended session times, as `HHMM`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
+    where D: Deserializer<'de>
+{
+    use serde::de::Error;
+
+    let s = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(&s, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&s, "%H%M"))
+        .map_err(Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Session {
+        #[serde(with = "super")]
+        open: NaiveTime,
+    }
+
+    fn decode(s: &str) -> Result<NaiveTime, serde_json::Error> {
+        serde_json::from_value::<Session>(json!({ "open": s })).map(|s| s.open)
+    }
+
+    #[test]
+    fn decodes_both_formats() {
+        let open = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(decode("09:30").unwrap(), open);
+        assert_eq!(decode("0930").unwrap(), open);
+        assert_eq!(decode("2000").unwrap(), NaiveTime::from_hms_opt(20, 0, 0).unwrap());
+        assert!(decode("9:30am").is_err());
+    }
+
+    #[test]
+    fn encodes_as_hours_and_minutes() {
+        let session = Session { open: NaiveTime::from_hms_opt(4, 0, 0).unwrap() };
+        assert_eq!(serde_json::to_value(&session).unwrap(), json!({ "open": "04:00" }));
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::Serializer;
+
+/// Serialize a list of times as unix timestamps, in seconds.
+pub fn serialize<S>(v: &[DateTime<Utc>], serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    serializer.collect_seq(v.iter().map(DateTime::timestamp))
+}
+
+/// Deserialize a list of unix timestamps, in seconds.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<DateTime<Utc>>, D::Error>
+    where D: Deserializer<'de>
+{
+    Vec::<i64>::deserialize(deserializer)?
+        .into_iter()
+        .map(|secs| Utc.timestamp_opt(secs, 0).single()
+            .ok_or_else(|| de::Error::custom(format!("invalid timestamp: {}", secs))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct History {
+        #[serde(with = "super")]
+        timestamp: Vec<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn round_trips_unix_seconds() {
+        let history: History = serde_json::from_value(json!({
+            "timestamp": [1580826600, 1580913000],
+        })).unwrap();
+
+        assert_eq!(history.timestamp, vec![
+            Utc.with_ymd_and_hms(2020, 2, 4, 14, 30, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 2, 5, 14, 30, 0).unwrap(),
+        ]);
+        assert_eq!(serde_json::to_value(&history).unwrap(),
+                   json!({ "timestamp": [1580826600, 1580913000] }));
+    }
+
+    #[test]
+    fn rejects_out_of_range_timestamps() {
+        let history = serde_json::from_value::<History>(json!({ "timestamp": [i64::MAX] }));
+        assert!(history.is_err());
+    }
+}
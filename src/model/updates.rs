@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Order, OrderStatus};
+
+/// The kind of event reported by the `trade_updates` stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeEvent {
+    /// Sent when an order has been routed to exchanges for execution.
+    New,
+    /// Sent when an order has been completely filled.
+    Fill,
+    /// Sent when a number of shares less than the total remaining
+    /// quantity on your order has been filled.
+    PartialFill,
+    /// Sent when your requested cancellation of an order is processed.
+    Canceled,
+    /// Sent when an order has reached the end of its lifespan, as
+    /// determined by the order’s time in force value.
+    Expired,
+    /// Sent when the order is done executing for the day, and will not
+    /// receive further updates until the next trading day.
+    DoneForDay,
+    /// Sent when your requested replacement of an order is processed.
+    Replaced,
+    /// Sent when your order has been rejected.
+    Rejected,
+    /// Sent when the order has been received by Alpaca and routed to the
+    /// exchanges, but has not yet been accepted for execution.
+    PendingNew,
+    /// Sent when your order has been stopped, and a trade is guaranteed
+    /// for the order, usually at a stated price or better, but has not
+    /// yet occurred.
+    Stopped,
+    /// Sent when the order is awaiting cancellation.
+    PendingCancel,
+    /// Sent when the order is awaiting replacement.
+    PendingReplace,
+    /// Sent when the order has been completed for the day, but remaining
+    /// settlement calculations are still pending.
+    Calculated,
+    /// Sent when the order has been suspended and is not eligible for
+    /// trading.
+    Suspended,
+    /// Sent when the order replace has been rejected.
+    OrderReplaceRejected,
+    /// Sent when the order cancel has been rejected.
+    OrderCancelRejected,
+}
+
+/// An update to one of the account's orders, as sent on the
+/// `trade_updates` stream.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TradeUpdate {
+    /// What happened to the order.
+    pub event: TradeEvent,
+    /// The time at which the event occurred. Sent with fills,
+    /// cancellations, expirations and replacements.
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// The price per share of a fill.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub price: Option<Decimal>,
+    /// The number of shares traded by a fill.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub qty: Option<Decimal>,
+    /// The total size of the position after a fill.
+    #[serde(with = "super::serde::decimal_optional")]
+    #[serde(default)]
+    pub position_qty: Option<Decimal>,
+    /// The order, as of this event.
+    pub order: Order,
+    /// True if the update was not sent by alpaca, but reconstructed from
+    /// the order itself, for example to catch up after the stream was
+    /// disconnected. Such updates carry no fill price or quantities.
+    #[serde(skip)]
+    pub synthetic: bool,
+}
+
+impl TradeUpdate {
+    /// Build a synthetic update that reports the current state of
+    /// `order`.
+    pub fn from_order(order: Order) -> TradeUpdate {
+        TradeUpdate {
+            event: order.status.into(),
+            timestamp: order.updated_at,
+            price: None,
+            qty: None,
+            position_qty: None,
+            order,
+            synthetic: true,
+        }
+    }
+}
+
+impl From<OrderStatus> for TradeEvent {
+    /// The event that moves an order into `status`.
+    fn from(status: OrderStatus) -> TradeEvent {
+        match status {
+            OrderStatus::New => TradeEvent::New,
+            OrderStatus::PartiallyFilled => TradeEvent::PartialFill,
+            OrderStatus::Filled => TradeEvent::Fill,
+            OrderStatus::DoneForDay => TradeEvent::DoneForDay,
+            OrderStatus::Canceled => TradeEvent::Canceled,
+            OrderStatus::Expired => TradeEvent::Expired,
+            OrderStatus::Replaced => TradeEvent::Replaced,
+            OrderStatus::PendingCancel => TradeEvent::PendingCancel,
+            OrderStatus::PendingReplace => TradeEvent::PendingReplace,
+            OrderStatus::Accepted => TradeEvent::PendingNew,
+            OrderStatus::PendingNew => TradeEvent::PendingNew,
+            OrderStatus::AcceptedForBidding => TradeEvent::New,
+            OrderStatus::Stopped => TradeEvent::Stopped,
+            OrderStatus::Rejected => TradeEvent::Rejected,
+            OrderStatus::Suspended => TradeEvent::Suspended,
+            OrderStatus::Calculated => TradeEvent::Calculated,
+            // Alpaca has no event for held legs, which have not been
+            // routed yet.
+            OrderStatus::Held => TradeEvent::PendingNew,
+        }
+    }
+}
@@ -0,0 +1,629 @@
+use std::fmt;
+
+use super::{
+    Amount, ClosePosition, Decimal, DecimalError, IntoDecimal, OrderClass, OrderReplace,
+    OrderRequest, OrderType, Side, TimeInForce,
+};
+
+/// A reason alpaca would reject an `OrderRequest`, found by
+/// `OrderRequest::validate` before the order is sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OrderViolation {
+    /// A value given to a builder could not be converted to a decimal.
+    /// Holds the name of the field.
+    InvalidNumber(&'static str, DecimalError),
+    /// The quantity or notional amount is zero or negative.
+    NonPositiveAmount(Amount),
+    /// A price field is zero or negative. Holds the name of the field.
+    NonPositivePrice(&'static str),
+    /// Limit and stop limit orders need a `limit_price`.
+    MissingLimitPrice(OrderType),
+    /// Stop and stop limit orders need a `stop_price`.
+    MissingStopPrice(OrderType),
+    /// Trailing stop orders need a `trail_price` or `trail_percent`.
+    MissingTrail,
+    /// `limit_price` is only allowed on limit and stop limit orders.
+    UnexpectedLimitPrice(OrderType),
+    /// `stop_price` is only allowed on stop and stop limit orders.
+    UnexpectedStopPrice(OrderType),
+    /// `trail_price` and `trail_percent` are only allowed on trailing
+    /// stop orders.
+    UnexpectedTrail(OrderType),
+    /// Only one of `trail_price` and `trail_percent` may be set.
+    ConflictingTrail,
+    /// The time in force cannot be used with the order type. For example,
+    /// OPG and CLS are only allowed for market and limit orders.
+    UnsupportedTimeInForce(OrderType, TimeInForce),
+    /// Fractional and notional orders must be market orders.
+    FractionalOrderType(OrderType),
+    /// Fractional and notional orders must be day orders.
+    FractionalTimeInForce(TimeInForce),
+    /// Extended hours orders must be limit orders.
+    ExtendedHoursOrderType(OrderType),
+    /// Extended hours orders must be day orders.
+    ExtendedHoursTimeInForce(TimeInForce),
+    /// The order class needs a `take_profit` leg.
+    MissingTakeProfit(OrderClass),
+    /// The order class needs a `stop_loss` leg.
+    MissingStopLoss(OrderClass),
+    /// One-triggers-other orders need a `take_profit` or a `stop_loss`
+    /// leg.
+    MissingLeg,
+    /// One-triggers-other orders take either a `take_profit` or a
+    /// `stop_loss` leg, not both.
+    ConflictingLegs,
+    /// `take_profit` and `stop_loss` are only allowed on bracket, OCO and
+    /// OTO orders.
+    UnexpectedLegs,
+    /// Bracket, OCO and OTO orders must be day or GTC orders.
+    LegTimeInForce(TimeInForce),
+    /// The take-profit price must be above the stop loss price for buy
+    /// orders, and below it for sell orders.
+    LegsCrossed {
+        take_profit: Decimal,
+        stop_loss: Decimal,
+    },
+    /// The take-profit price must be above the entry price for buy orders,
+    /// and below it for sell orders.
+    TakeProfitPrice {
+        entry: Decimal,
+        take_profit: Decimal,
+    },
+    /// The stop loss price must be below the entry price for buy orders,
+    /// and above it for sell orders.
+    StopLossPrice {
+        entry: Decimal,
+        stop_loss: Decimal,
+    },
+    /// The limit price of a stop loss must be at or below its stop price
+    /// for buy orders, and at or above it for sell orders.
+    StopLossLimitPrice {
+        stop_price: Decimal,
+        limit_price: Decimal,
+    },
+}
+
+impl fmt::Display for OrderViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderViolation::InvalidNumber(field, error) =>
+                write!(f, "{}: {}", field, error),
+            OrderViolation::NonPositiveAmount(Amount::Qty(qty)) =>
+                write!(f, "qty must be positive, not {}", qty),
+            OrderViolation::NonPositiveAmount(Amount::Notional(notional)) =>
+                write!(f, "notional must be positive, not {}", notional),
+            OrderViolation::NonPositivePrice(field) =>
+                write!(f, "{} must be positive", field),
+            OrderViolation::MissingLimitPrice(order_type) =>
+                write!(f, "{:?} orders require a limit price", order_type),
+            OrderViolation::MissingStopPrice(order_type) =>
+                write!(f, "{:?} orders require a stop price", order_type),
+            OrderViolation::MissingTrail =>
+                write!(f, "trailing stop orders require a trail price or trail percent"),
+            OrderViolation::UnexpectedLimitPrice(order_type) =>
+                write!(f, "{:?} orders do not take a limit price", order_type),
+            OrderViolation::UnexpectedStopPrice(order_type) =>
+                write!(f, "{:?} orders do not take a stop price", order_type),
+            OrderViolation::UnexpectedTrail(order_type) =>
+                write!(f, "{:?} orders do not take a trail", order_type),
+            OrderViolation::ConflictingTrail =>
+                write!(f, "only one of trail price and trail percent may be set"),
+            OrderViolation::UnsupportedTimeInForce(order_type, time_in_force) =>
+                write!(f, "{:?} orders cannot be {:?} orders", order_type, time_in_force),
+            OrderViolation::FractionalOrderType(order_type) =>
+                write!(f, "fractional and notional orders must be market orders, \
+                           not {:?}", order_type),
+            OrderViolation::FractionalTimeInForce(time_in_force) =>
+                write!(f, "fractional and notional orders must be day orders, \
+                           not {:?}", time_in_force),
+            OrderViolation::ExtendedHoursOrderType(order_type) =>
+                write!(f, "extended hours orders must be limit orders, not {:?}", order_type),
+            OrderViolation::ExtendedHoursTimeInForce(time_in_force) =>
+                write!(f, "extended hours orders must be day orders, not {:?}", time_in_force),
+            OrderViolation::MissingTakeProfit(order_class) =>
+                write!(f, "{:?} orders require a take profit", order_class),
+            OrderViolation::MissingStopLoss(order_class) =>
+                write!(f, "{:?} orders require a stop loss", order_class),
+            OrderViolation::MissingLeg =>
+                write!(f, "OTO orders require a take profit or a stop loss"),
+            OrderViolation::ConflictingLegs =>
+                write!(f, "OTO orders take a take profit or a stop loss, not both"),
+            OrderViolation::UnexpectedLegs =>
+                write!(f, "only bracket, OCO and OTO orders take a take profit or stop loss"),
+            OrderViolation::LegTimeInForce(time_in_force) =>
+                write!(f, "multi-leg orders must be day or GTC orders, not {:?}", time_in_force),
+            OrderViolation::LegsCrossed { take_profit, stop_loss } =>
+                write!(f, "take profit at {} is on the wrong side of stop loss at {}",
+                       take_profit, stop_loss),
+            OrderViolation::TakeProfitPrice { entry, take_profit } =>
+                write!(f, "take profit at {} is on the wrong side of the entry at {}",
+                       take_profit, entry),
+            OrderViolation::StopLossPrice { entry, stop_loss } =>
+                write!(f, "stop loss at {} is on the wrong side of the entry at {}",
+                       stop_loss, entry),
+            OrderViolation::StopLossLimitPrice { stop_price, limit_price } =>
+                write!(f, "stop loss limit price {} is on the wrong side of its stop price {}",
+                       limit_price, stop_price),
+        }
+    }
+}
+
+/// Convert `value` for `field`. If it is not a decimal, it is recorded in
+/// `invalid` and `None` is returned. A value recorded for `field` earlier
+/// is forgotten either way, since it has been replaced.
+pub(crate) fn convert<T>(invalid: &mut Vec<OrderViolation>, field: &'static str, value: T)
+        -> Option<Decimal>
+    where T: IntoDecimal
+{
+    forget(invalid, field);
+
+    match value.into_decimal() {
+        Ok(value) => Some(value),
+        Err(error) => {
+            invalid.push(OrderViolation::InvalidNumber(field, error));
+            None
+        },
+    }
+}
+
+/// Forget the value recorded in `invalid` for `field`, if any.
+pub(crate) fn forget(invalid: &mut Vec<OrderViolation>, field: &'static str) {
+    invalid.retain(|violation| match violation {
+        OrderViolation::InvalidNumber(name, _) => *name != field,
+        _ => true,
+    });
+}
+
+/// Fail with `invalid`, unless it is empty.
+fn check_numbers(invalid: &[OrderViolation]) -> Result<(), Vec<OrderViolation>> {
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid.to_vec())
+    }
+}
+
+impl OrderRequest {
+    /// Check the order against the rules alpaca enforces, and list every
+    /// rule it breaks. `Client::request_order` runs this before sending
+    /// the order.
+    ///
+    /// Values that could not be converted to decimals are reported on
+    /// their own, since the other rules cannot be checked without them.
+    pub fn validate(&self) -> Result<(), Vec<OrderViolation>> {
+        self.check_numbers()?;
+
+        let mut violations = Vec::new();
+
+        self.check_amount(&mut violations);
+        self.check_prices(&mut violations);
+        self.check_time_in_force(&mut violations);
+        self.check_legs(&mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Fail with the values, including those of the legs, that could not
+    /// be converted to decimals.
+    pub(crate) fn check_numbers(&self) -> Result<(), Vec<OrderViolation>> {
+        let mut invalid = self.invalid.clone();
+        if let Some(take_profit) = &self.take_profit {
+            invalid.extend(take_profit.invalid.iter().cloned());
+        }
+        if let Some(stop_loss) = &self.stop_loss {
+            invalid.extend(stop_loss.invalid.iter().cloned());
+        }
+
+        check_numbers(&invalid)
+    }
+
+    fn check_amount(&self, violations: &mut Vec<OrderViolation>) {
+        let amount = match self.amount {
+            Amount::Qty(qty) => qty,
+            Amount::Notional(notional) => notional,
+        };
+
+        if amount <= Decimal::ZERO {
+            violations.push(OrderViolation::NonPositiveAmount(self.amount));
+        }
+
+        if self.amount.is_fractional() {
+            if self.order_type != OrderType::Market {
+                violations.push(OrderViolation::FractionalOrderType(self.order_type));
+            }
+            if self.time_in_force != TimeInForce::Day {
+                violations.push(OrderViolation::FractionalTimeInForce(self.time_in_force));
+            }
+        }
+    }
+
+    fn check_prices(&self, violations: &mut Vec<OrderViolation>) {
+        let order_type = self.order_type;
+        // The limit price of an OCO order is given by its take-profit leg.
+        let oco = self.order_class == Some(OrderClass::Oco);
+        let (limit, stop, trail) = match order_type {
+            OrderType::Market => (false, false, false),
+            OrderType::Limit if oco => (self.limit_price.is_some(), false, false),
+            OrderType::Limit => (true, false, false),
+            OrderType::Stop => (false, true, false),
+            OrderType::StopLimit => (true, true, false),
+            OrderType::TrailingStop => (false, false, true),
+        };
+
+        match (limit, self.limit_price) {
+            (true, None) => violations.push(OrderViolation::MissingLimitPrice(order_type)),
+            (false, Some(_)) => violations.push(OrderViolation::UnexpectedLimitPrice(order_type)),
+            _ => {},
+        }
+
+        match (stop, self.stop_price) {
+            (true, None) => violations.push(OrderViolation::MissingStopPrice(order_type)),
+            (false, Some(_)) => violations.push(OrderViolation::UnexpectedStopPrice(order_type)),
+            _ => {},
+        }
+
+        match (trail, self.trail_price, self.trail_percent) {
+            (true, None, None) => violations.push(OrderViolation::MissingTrail),
+            (true, Some(_), Some(_)) => violations.push(OrderViolation::ConflictingTrail),
+            (false, Some(_), _) | (false, _, Some(_)) =>
+                violations.push(OrderViolation::UnexpectedTrail(order_type)),
+            _ => {},
+        }
+
+        let prices = [
+            ("limit_price", self.limit_price),
+            ("stop_price", self.stop_price),
+            ("trail_price", self.trail_price),
+            ("trail_percent", self.trail_percent),
+            ("take_profit.limit_price", self.take_profit.as_ref().map(|leg| leg.limit_price)),
+            ("stop_loss.stop_price", self.stop_loss.as_ref().map(|leg| leg.stop_price)),
+            ("stop_loss.limit_price", self.stop_loss.as_ref().and_then(|leg| leg.limit_price)),
+        ];
+
+        for &(field, price) in prices.iter() {
+            if matches!(price, Some(price) if price <= Decimal::ZERO) {
+                violations.push(OrderViolation::NonPositivePrice(field));
+            }
+        }
+    }
+
+    fn check_time_in_force(&self, violations: &mut Vec<OrderViolation>) {
+        let allowed = match self.time_in_force {
+            TimeInForce::Opg | TimeInForce::Cls =>
+                matches!(self.order_type, OrderType::Market | OrderType::Limit),
+            TimeInForce::Ioc | TimeInForce::Fok =>
+                self.order_type != OrderType::TrailingStop,
+            TimeInForce::Day | TimeInForce::Gtc => true,
+        };
+
+        if !allowed {
+            violations.push(OrderViolation::UnsupportedTimeInForce(
+                self.order_type, self.time_in_force));
+        }
+
+        if self.extended_hours == Some(true) {
+            if self.order_type != OrderType::Limit {
+                violations.push(OrderViolation::ExtendedHoursOrderType(self.order_type));
+            }
+            if self.time_in_force != TimeInForce::Day {
+                violations.push(OrderViolation::ExtendedHoursTimeInForce(self.time_in_force));
+            }
+        }
+    }
+
+    fn check_legs(&self, violations: &mut Vec<OrderViolation>) {
+        let class = self.order_class.unwrap_or(OrderClass::Simple);
+        let take_profit = self.take_profit.as_ref().map(|leg| leg.limit_price);
+        let stop_loss = self.stop_loss.as_ref().map(|leg| leg.stop_price);
+
+        match class {
+            OrderClass::Simple => {
+                if take_profit.is_some() || stop_loss.is_some() {
+                    violations.push(OrderViolation::UnexpectedLegs);
+                }
+                return;
+            },
+            OrderClass::Bracket | OrderClass::Oco => {
+                if take_profit.is_none() {
+                    violations.push(OrderViolation::MissingTakeProfit(class));
+                }
+                if stop_loss.is_none() {
+                    violations.push(OrderViolation::MissingStopLoss(class));
+                }
+            },
+            OrderClass::Oto => match (take_profit, stop_loss) {
+                (None, None) => violations.push(OrderViolation::MissingLeg),
+                (Some(_), Some(_)) => violations.push(OrderViolation::ConflictingLegs),
+                _ => {},
+            },
+        }
+
+        if !matches!(self.time_in_force, TimeInForce::Day | TimeInForce::Gtc) {
+            violations.push(OrderViolation::LegTimeInForce(self.time_in_force));
+        }
+
+        // The legs exit the position opened by this order, so for a buy
+        // order the take-profit sits above the stop loss, and the other
+        // way around for a sell order. An OCO order is itself an exit, so
+        // its side is that of its legs.
+        let long = match class {
+            OrderClass::Oco => self.side == Side::Sell,
+            _ => self.side == Side::Buy,
+        };
+        let above = |a: Decimal, b: Decimal| if long { a > b } else { a < b };
+
+        if let (Some(take_profit), Some(stop_loss)) = (take_profit, stop_loss) {
+            if !above(take_profit, stop_loss) {
+                violations.push(OrderViolation::LegsCrossed { take_profit, stop_loss });
+            }
+        }
+
+        if let (Some(entry), true) = (self.limit_price, class != OrderClass::Oco) {
+            if let Some(take_profit) = take_profit {
+                if !above(take_profit, entry) {
+                    violations.push(OrderViolation::TakeProfitPrice { entry, take_profit });
+                }
+            }
+            if let Some(stop_loss) = stop_loss {
+                if !above(entry, stop_loss) {
+                    violations.push(OrderViolation::StopLossPrice { entry, stop_loss });
+                }
+            }
+        }
+
+        if let Some(leg) = &self.stop_loss {
+            if let Some(limit_price) = leg.limit_price {
+                if above(limit_price, leg.stop_price) {
+                    violations.push(OrderViolation::StopLossLimitPrice {
+                        stop_price: leg.stop_price,
+                        limit_price,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl OrderReplace {
+    /// Fail with the values that could not be converted to decimals.
+    /// `Client::replace_order` runs this before sending the request.
+    pub fn validate(&self) -> Result<(), Vec<OrderViolation>> {
+        check_numbers(&self.invalid)
+    }
+}
+
+impl ClosePosition {
+    /// Fail with the values that could not be converted to decimals.
+    /// `Client::close_position` runs this before sending the request.
+    pub fn validate(&self) -> Result<(), Vec<OrderViolation>> {
+        check_numbers(&self.invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{OrderStopLoss, OrderTakeProfit};
+
+    fn violations(order: &OrderRequest) -> Vec<OrderViolation> {
+        order.validate().err().unwrap_or_default()
+    }
+
+    fn limit_buy(limit_price: i32) -> OrderRequest {
+        OrderRequest::buy("AAPL", 1)
+            .order_type(OrderType::Limit)
+            .limit_price(limit_price)
+    }
+
+    #[test]
+    fn valid_orders() {
+        assert_eq!(OrderRequest::buy("AAPL", 1).validate(), Ok(()));
+        assert_eq!(limit_buy(100).time_in_force(TimeInForce::Gtc).validate(), Ok(()));
+        assert_eq!(OrderRequest::buy_notional("AAPL", "250.5").validate(), Ok(()));
+
+        let bracket = OrderRequest::bracket(
+            limit_buy(100), OrderTakeProfit::new(110), OrderStopLoss::new(90).limit_price(89));
+        assert!(bracket.is_ok());
+
+        let bracket = OrderRequest::bracket(
+            OrderRequest::sell("AAPL", 1).order_type(OrderType::Limit).limit_price(100),
+            OrderTakeProfit::new(90),
+            OrderStopLoss::new(110));
+        assert!(bracket.is_ok());
+
+        let oco = OrderRequest::oco(
+            OrderRequest::sell("AAPL", 1), OrderTakeProfit::new(110), OrderStopLoss::new(90));
+        assert!(oco.is_ok());
+    }
+
+    #[test]
+    fn invalid_number() {
+        let order = OrderRequest::buy("AAPL", "abc").order_type(OrderType::Limit);
+        let found = violations(&order);
+        assert_eq!(found.len(), 1);
+        assert!(matches!(&found[0],
+            OrderViolation::InvalidNumber("qty", error) if error.input() == "abc"));
+
+        let order = limit_buy(100).limit_price(f64::NAN);
+        assert!(matches!(violations(&order).as_slice(),
+            [OrderViolation::InvalidNumber("limit_price", _)]));
+
+        let order = OrderRequest::buy("AAPL", 1)
+            .take_profit(OrderTakeProfit::new(f64::INFINITY))
+            .order_class(OrderClass::Oto);
+        assert!(matches!(violations(&order).as_slice(),
+            [OrderViolation::InvalidNumber("take_profit.limit_price", _)]));
+
+        // A valid value replaces the invalid one.
+        let order = limit_buy(100).limit_price("x").limit_price(100);
+        assert_eq!(order.validate(), Ok(()));
+        let order = OrderRequest::buy("AAPL", "x").notional(100);
+        assert_eq!(order.validate(), Ok(()));
+    }
+
+    #[test]
+    fn non_positive_amount() {
+        assert_eq!(violations(&OrderRequest::buy("AAPL", 0)),
+                   vec![OrderViolation::NonPositiveAmount(Amount::Qty(Decimal::ZERO))]);
+        assert_eq!(violations(&OrderRequest::sell_notional("AAPL", -5)),
+                   vec![OrderViolation::NonPositiveAmount(Amount::Notional(Decimal::from(-5)))]);
+    }
+
+    #[test]
+    fn non_positive_price() {
+        assert_eq!(violations(&limit_buy(0)),
+                   vec![OrderViolation::NonPositivePrice("limit_price")]);
+    }
+
+    #[test]
+    fn missing_prices() {
+        let order = OrderRequest::buy("AAPL", 1).order_type(OrderType::Limit);
+        assert_eq!(violations(&order), vec![OrderViolation::MissingLimitPrice(OrderType::Limit)]);
+
+        let order = OrderRequest::buy("AAPL", 1).order_type(OrderType::StopLimit);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::MissingLimitPrice(OrderType::StopLimit),
+            OrderViolation::MissingStopPrice(OrderType::StopLimit),
+        ]);
+
+        let order = OrderRequest::buy("AAPL", 1).order_type(OrderType::TrailingStop);
+        assert_eq!(violations(&order), vec![OrderViolation::MissingTrail]);
+    }
+
+    #[test]
+    fn unexpected_prices() {
+        let order = OrderRequest::buy("AAPL", 1).limit_price(1).stop_price(1);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::UnexpectedLimitPrice(OrderType::Market),
+            OrderViolation::UnexpectedStopPrice(OrderType::Market),
+        ]);
+
+        let order = limit_buy(100).trail_percent(1);
+        assert_eq!(violations(&order), vec![OrderViolation::UnexpectedTrail(OrderType::Limit)]);
+    }
+
+    #[test]
+    fn conflicting_trail() {
+        let mut order = OrderRequest::buy("AAPL", 1)
+            .order_type(OrderType::TrailingStop)
+            .trail_price(1);
+        order.trail_percent = Some(Decimal::from(1));
+        assert_eq!(violations(&order), vec![OrderViolation::ConflictingTrail]);
+    }
+
+    #[test]
+    fn unsupported_time_in_force() {
+        let order = OrderRequest::buy("AAPL", 1)
+            .order_type(OrderType::Stop)
+            .stop_price(100)
+            .time_in_force(TimeInForce::Opg);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::UnsupportedTimeInForce(OrderType::Stop, TimeInForce::Opg),
+        ]);
+    }
+
+    #[test]
+    fn fractional_orders() {
+        let order = OrderRequest::buy("AAPL", "0.5")
+            .order_type(OrderType::Limit)
+            .limit_price(100);
+        assert_eq!(violations(&order), vec![OrderViolation::FractionalOrderType(OrderType::Limit)]);
+
+        let order = OrderRequest::buy_notional("AAPL", 100).time_in_force(TimeInForce::Gtc);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::FractionalTimeInForce(TimeInForce::Gtc),
+        ]);
+    }
+
+    #[test]
+    fn extended_hours() {
+        let order = OrderRequest::buy("AAPL", 1).extended_hours(true);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::ExtendedHoursOrderType(OrderType::Market),
+        ]);
+
+        let order = limit_buy(100).extended_hours(true).time_in_force(TimeInForce::Gtc);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::ExtendedHoursTimeInForce(TimeInForce::Gtc),
+        ]);
+    }
+
+    #[test]
+    fn missing_legs() {
+        let order = limit_buy(100).order_class(OrderClass::Bracket);
+        assert_eq!(violations(&order), vec![
+            OrderViolation::MissingTakeProfit(OrderClass::Bracket),
+            OrderViolation::MissingStopLoss(OrderClass::Bracket),
+        ]);
+
+        let order = limit_buy(100).order_class(OrderClass::Oto);
+        assert_eq!(violations(&order), vec![OrderViolation::MissingLeg]);
+    }
+
+    #[test]
+    fn conflicting_and_unexpected_legs() {
+        let order = limit_buy(100)
+            .order_class(OrderClass::Oto)
+            .take_profit(OrderTakeProfit::new(110))
+            .stop_loss(OrderStopLoss::new(90));
+        assert_eq!(violations(&order), vec![OrderViolation::ConflictingLegs]);
+
+        let order = limit_buy(100).take_profit(OrderTakeProfit::new(110));
+        assert_eq!(violations(&order), vec![OrderViolation::UnexpectedLegs]);
+    }
+
+    #[test]
+    fn leg_time_in_force() {
+        let order = OrderRequest::bracket(
+            limit_buy(100).time_in_force(TimeInForce::Ioc),
+            OrderTakeProfit::new(110),
+            OrderStopLoss::new(90));
+        assert_eq!(order.err(), Some(vec![OrderViolation::LegTimeInForce(TimeInForce::Ioc)]));
+    }
+
+    #[test]
+    fn leg_prices() {
+        let order = OrderRequest::bracket(
+            OrderRequest::buy("AAPL", 1), OrderTakeProfit::new(90), OrderStopLoss::new(100));
+        assert_eq!(order.err(), Some(vec![OrderViolation::LegsCrossed {
+            take_profit: Decimal::from(90),
+            stop_loss: Decimal::from(100),
+        }]));
+
+        let order = OrderRequest::bracket(
+            limit_buy(100), OrderTakeProfit::new(95), OrderStopLoss::new(90));
+        assert_eq!(order.err(), Some(vec![OrderViolation::TakeProfitPrice {
+            entry: Decimal::from(100),
+            take_profit: Decimal::from(95),
+        }]));
+
+        let order = OrderRequest::bracket(
+            limit_buy(100), OrderTakeProfit::new(110), OrderStopLoss::new(105));
+        assert_eq!(order.err(), Some(vec![OrderViolation::StopLossPrice {
+            entry: Decimal::from(100),
+            stop_loss: Decimal::from(105),
+        }]));
+
+        let order = OrderRequest::bracket(
+            limit_buy(100), OrderTakeProfit::new(110), OrderStopLoss::new(90).limit_price(95));
+        assert_eq!(order.err(), Some(vec![OrderViolation::StopLossLimitPrice {
+            stop_price: Decimal::from(90),
+            limit_price: Decimal::from(95),
+        }]));
+    }
+
+    #[test]
+    fn replace_and_close_position() {
+        assert_eq!(OrderReplace::new().qty(2).limit_price("1e2").validate(), Ok(()));
+        let found = OrderReplace::new().trail_price(f64::NAN).validate().unwrap_err();
+        assert!(matches!(found.as_slice(), [OrderViolation::InvalidNumber("trail", _)]));
+
+        assert_eq!(ClosePosition::percentage(50).validate(), Ok(()));
+        let found = ClosePosition::qty("all").validate().unwrap_err();
+        assert!(matches!(found.as_slice(), [OrderViolation::InvalidNumber("qty", _)]));
+    }
+}
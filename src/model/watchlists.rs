@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Asset;
+
+/// A named, ordered list of assets.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Watchlist {
+    /// Watchlist id.
+    pub id: Uuid,
+    /// Account id.
+    pub account_id: Uuid,
+    /// User-defined watchlist name.
+    pub name: String,
+    /// Timestamp the watchlist was created at.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp the watchlist was last changed at.
+    pub updated_at: DateTime<Utc>,
+    /// The content of the watchlist, in the order defined by the user.
+    /// Not reported when listing watchlists.
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+/// The name and content of a watchlist, used to create or replace one.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WatchlistRequest {
+    /// User-defined watchlist name, up to 64 characters.
+    pub name: String,
+    /// The symbols in the watchlist. When replacing a watchlist, `None`
+    /// only renames it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbols: Option<Vec<String>>,
+}
+
+impl WatchlistRequest {
+    pub fn new<T>(name: T) -> WatchlistRequest
+    where
+        T: Into<String>,
+    {
+        WatchlistRequest {
+            name: name.into(),
+            symbols: None,
+        }
+    }
+
+    pub fn symbols<I, T>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.symbols = Some(symbols.into_iter().map(Into::into).collect());
+        self
+    }
+}
@@ -0,0 +1,281 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::future::{self, AbortHandle, BoxFuture};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use url::Url;
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::model::{Bar, DataFeed, MarketData, Quote, Subscription, Trade};
+
+use super::{Handler, WebSocket};
+
+/// Messages sent to the market data stream.
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum Request<'a> {
+    Auth {
+        key: &'a str,
+        secret: &'a str,
+    },
+    Subscribe(&'a Subscription),
+    Unsubscribe(&'a Subscription),
+}
+
+/// Messages received from the market data stream. Every frame holds a
+/// list of these.
+#[derive(Deserialize)]
+#[serde(tag = "T")]
+enum Response {
+    #[serde(rename = "success")]
+    Success {
+        msg: String,
+    },
+    #[serde(rename = "error")]
+    Error {
+        code: i32,
+        msg: String,
+    },
+    #[serde(rename = "subscription")]
+    Subscription(Subscription),
+    #[serde(rename = "t")]
+    Trade(Trade),
+    #[serde(rename = "q")]
+    Quote(Quote),
+    #[serde(rename = "b")]
+    Bar(Bar),
+    #[serde(other)]
+    Other,
+}
+
+/// Changes to the subscription, sent from `MarketDataStream` to its
+/// session.
+enum Command {
+    Subscribe(Subscription),
+    Unsubscribe(Subscription),
+}
+
+/// A stream of live trades, quotes and minute bars. Created with
+/// `Client::market_data`.
+///
+/// Symbols can be added and removed at any time with `subscribe` and
+/// `unsubscribe`. Like `TradeUpdates`, the connection is supervised in
+/// the background, and is re-established and re-subscribed whenever it
+/// is lost. `Err` items report errors sent by alpaca, such as an invalid
+/// symbol, and do not end the stream.
+pub struct MarketDataStream {
+    commands: mpsc::UnboundedSender<Command>,
+    receiver: mpsc::UnboundedReceiver<Result<MarketData>>,
+    abort: AbortHandle,
+}
+
+impl MarketDataStream {
+    /// Start receiving the symbols in `subscription`, on top of the
+    /// current ones.
+    pub fn subscribe(&self, subscription: Subscription) -> Result<()> {
+        self.command(Command::Subscribe(subscription))
+    }
+
+    /// Stop receiving the symbols in `subscription`.
+    pub fn unsubscribe(&self, subscription: Subscription) -> Result<()> {
+        self.command(Command::Unsubscribe(subscription))
+    }
+
+    fn command(&self, command: Command) -> Result<()> {
+        self.commands.send(command)
+            .map_err(|_| Error::Stream("market data stream is closed".into()))
+    }
+}
+
+impl Stream for MarketDataStream {
+    type Item = Result<MarketData>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>)
+            -> Poll<Option<Self::Item>>
+    {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for MarketDataStream {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+impl Client {
+    /// Connect to the market data stream of `feed`. The stream starts
+    /// without any subscriptions.
+    pub async fn market_data(&self, feed: DataFeed) -> Result<MarketDataStream> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let (commands, command_receiver) = mpsc::unbounded_channel();
+
+        let url = self.data_stream_endpoint
+            .join(&format!("/v2/{}", feed.as_str()))?;
+
+        let mut session = Session {
+            client: self.clone(),
+            url,
+            subscription: Subscription::new(),
+            sender,
+        };
+
+        let ws = session.connect().await?;
+        let (task, abort) = future::abortable(async move {
+            super::supervise(&mut session, ws, command_receiver).await
+        });
+
+        tokio::spawn(task);
+        Ok(MarketDataStream { commands, receiver, abort })
+    }
+}
+
+/// The background half of `MarketDataStream`.
+struct Session {
+    client: Client,
+    url: Url,
+    /// The symbols the user asked for, restored after a reconnect.
+    subscription: Subscription,
+    sender: mpsc::UnboundedSender<Result<MarketData>>,
+}
+
+impl Handler for Session {
+    type Command = Command;
+
+    /// Open and authenticate a connection, and restore the subscription.
+    fn connect(&self) -> BoxFuture<'_, Result<WebSocket>> {
+        Box::pin(async move {
+            let mut ws = super::connect(&self.url).await?;
+
+            // Alpaca greets every new connection before anything else.
+            expect_success(&mut ws, "connected").await?;
+
+            let auth = Request::Auth {
+                key: &self.client.key_id,
+                secret: &self.client.secret_key,
+            };
+
+            super::send(&mut ws, &auth).await?;
+            expect_success(&mut ws, "authenticated").await?;
+
+            if !self.subscription.is_empty() {
+                super::send(&mut ws, &Request::Subscribe(&self.subscription)).await?;
+            }
+
+            Ok(ws)
+        })
+    }
+
+    fn frame(&mut self, text: String) -> bool {
+        self.forward(text)
+    }
+
+    fn command(&mut self, command: Command) -> Result<String> {
+        match command {
+            Command::Subscribe(subscription) => {
+                add(&mut self.subscription, &subscription);
+                super::encode(&Request::Subscribe(&subscription))
+            },
+            Command::Unsubscribe(subscription) => {
+                remove(&mut self.subscription, &subscription);
+                super::encode(&Request::Unsubscribe(&subscription))
+            },
+        }
+    }
+
+    fn fail(&mut self, error: Error) {
+        let _ = self.sender.send(Err(error));
+    }
+}
+
+impl Session {
+    /// Decode a frame, and pass its contents on to the user. Returns false
+    /// once the `MarketDataStream` handle has been dropped.
+    fn forward(&self, text: String) -> bool {
+        let responses = match super::decode::<Vec<Response>>(text) {
+            Ok(responses) => responses,
+            Err(e) => return self.sender.send(Err(e)).is_ok(),
+        };
+
+        for response in responses {
+            let item = match response {
+                Response::Trade(trade) => Ok(MarketData::Trade(trade)),
+                Response::Quote(quote) => Ok(MarketData::Quote(quote)),
+                Response::Bar(bar) => Ok(MarketData::Bar(bar)),
+                Response::Subscription(subscription) =>
+                    Ok(MarketData::Subscription(subscription)),
+                Response::Error { code, msg } =>
+                    Err(Error::Stream(format!("({}) {}", code, msg))),
+                Response::Success { .. } | Response::Other => continue,
+            };
+
+            if self.sender.send(item).is_err() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Error codes with which alpaca refuses the credentials: authentication
+/// failed (402), and a subscription that does not include the feed
+/// (409).
+const REFUSED: [i32; 2] = [402, 409];
+
+/// Wait for a success message with `msg`. Alpaca reports refused
+/// credentials and connection limits as error messages.
+async fn expect_success(ws: &mut WebSocket, msg: &str) -> Result<()> {
+    loop {
+        let text = match super::recv(ws).await? {
+            Some(text) => text,
+            None => return Err(Error::WebSocket(
+                tokio_tungstenite::tungstenite::Error::ConnectionClosed)),
+        };
+
+        for response in super::decode::<Vec<Response>>(text)? {
+            match response {
+                Response::Success { msg: m } if m == msg =>
+                    return Ok(()),
+                Response::Error { code, msg } if REFUSED.contains(&code) =>
+                    return Err(Error::StreamRefused(format!("({}) {}", code, msg))),
+                Response::Error { code, msg } =>
+                    return Err(Error::Stream(format!("({}) {}", code, msg))),
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// Add the symbols of `other` to `subscription`.
+fn add(subscription: &mut Subscription, other: &Subscription) {
+    let channels = vec![
+        (&mut subscription.trades, &other.trades),
+        (&mut subscription.quotes, &other.quotes),
+        (&mut subscription.bars, &other.bars),
+    ];
+
+    for (symbols, added) in channels {
+        for symbol in added {
+            if !symbols.contains(symbol) {
+                symbols.push(symbol.clone());
+            }
+        }
+    }
+}
+
+/// Remove the symbols of `other` from `subscription`.
+fn remove(subscription: &mut Subscription, other: &Subscription) {
+    let channels = vec![
+        (&mut subscription.trades, &other.trades),
+        (&mut subscription.quotes, &other.quotes),
+        (&mut subscription.bars, &other.bars),
+    ];
+
+    for (symbols, removed) in channels {
+        symbols.retain(|symbol| !removed.contains(symbol));
+    }
+}
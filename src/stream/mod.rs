@@ -0,0 +1,236 @@
+mod market_data;
+mod trading;
+
+pub use market_data::*;
+pub use trading::*;
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::future::{self, BoxFuture};
+use futures::{SinkExt, Stream, StreamExt};
+use serde::Serialize;
+use tokio::net::TcpStream;
+use tokio::time::{self, Instant};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::tungstenite::Message;
+use url::Url;
+
+use super::error::{Error, Result};
+
+type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How often a connection is pinged while no messages arrive.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long a connection may stay silent, pongs included, before it is
+/// considered dead.
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The first delay between reconnection attempts. The delay doubles
+/// after every failed attempt, up to `MAX_BACKOFF`.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The longest delay between reconnection attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The stream specific half of a supervised connection: how to connect,
+/// and what to do with the frames and commands that arrive.
+trait Handler {
+    /// Requests sent by the user's handle to the session.
+    type Command;
+
+    /// Open, authenticate and subscribe a connection.
+    fn connect(&self) -> BoxFuture<'_, Result<WebSocket>>;
+
+    /// Handle a text frame. Returns false once the user's handle has
+    /// been dropped.
+    fn frame(&mut self, text: String) -> bool;
+
+    /// Apply a command, and return the frame to send for it.
+    fn command(&mut self, command: Self::Command) -> Result<String>;
+
+    /// Catch up after a reconnect. `last_seen` is the time at which the
+    /// last frame was received before the connection was lost.
+    fn reconnected(&mut self, _last_seen: DateTime<Utc>) -> BoxFuture<'_, ()> {
+        Box::pin(future::ready(()))
+    }
+
+    /// Report an error that ends the stream.
+    fn fail(&mut self, error: Error);
+}
+
+/// Why `run` returned.
+enum Exit {
+    /// The connection was lost, or stopped answering.
+    Disconnected,
+    /// The user's handle was dropped.
+    Closed,
+}
+
+/// Pass frames from `ws` and `commands` to `handler`, reconnecting
+/// whenever the connection is lost. Returns once the user's handle is
+/// dropped, or alpaca refuses the credentials.
+async fn supervise<H, C>(handler: &mut H, mut ws: WebSocket, mut commands: C)
+    where H: Handler,
+          C: Stream<Item = H::Command> + Unpin,
+{
+    loop {
+        let mut last_seen = Utc::now();
+
+        if let Exit::Closed = run(handler, &mut ws, &mut commands, &mut last_seen).await {
+            return;
+        }
+
+        let connector: &H = handler;
+        ws = match reconnect(|| connector.connect()).await {
+            Ok(ws) => ws,
+            Err(e) => {
+                handler.fail(e);
+                return;
+            },
+        };
+
+        handler.reconnected(last_seen).await;
+    }
+}
+
+/// Pass frames and commands to `handler` until the connection is lost,
+/// pinging the server while it is quiet. `last_seen` is kept at the time
+/// the last frame was received.
+async fn run<H, C>(
+    handler: &mut H,
+    ws: &mut WebSocket,
+    commands: &mut C,
+    last_seen: &mut DateTime<Utc>,
+) -> Exit
+    where H: Handler,
+          C: Stream<Item = H::Command> + Unpin,
+{
+    let mut ticker = time::interval(PING_INTERVAL);
+    let mut heartbeat = Instant::now();
+
+    loop {
+        tokio::select! {
+            message = ws.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Binary(data))) => match String::from_utf8(data) {
+                        Ok(text) => text,
+                        Err(_) => continue,
+                    },
+                    Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => {
+                        heartbeat = Instant::now();
+                        continue;
+                    },
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None =>
+                        return Exit::Disconnected,
+                };
+
+                heartbeat = Instant::now();
+                *last_seen = Utc::now();
+
+                if !handler.frame(text) {
+                    return Exit::Closed;
+                }
+            },
+            command = commands.next() => {
+                let frame = match command {
+                    Some(command) => handler.command(command),
+                    None => return Exit::Closed,
+                };
+
+                // The handler has recorded the command, so it is applied
+                // again when the connection is restored.
+                let sent = match frame {
+                    Ok(text) => ws.send(Message::Text(text)).await,
+                    Err(_) => continue,
+                };
+
+                if sent.is_err() {
+                    return Exit::Disconnected;
+                }
+            },
+            _ = ticker.tick() => {
+                if heartbeat.elapsed() > HEARTBEAT_TIMEOUT {
+                    return Exit::Disconnected;
+                }
+                if ws.send(Message::Ping(Vec::new())).await.is_err() {
+                    return Exit::Disconnected;
+                }
+            },
+        }
+    }
+}
+
+/// Call `connect` until it succeeds, with exponential backoff between
+/// attempts. Only gives up if alpaca refuses the credentials, since
+/// retrying with them will not help. Other errors, such as exceeding
+/// the connection limit while the old connection is still being closed,
+/// are retried.
+async fn reconnect<F, T>(mut connect: F) -> Result<WebSocket>
+    where F: FnMut() -> T,
+          T: Future<Output = Result<WebSocket>>,
+{
+    let mut backoff = MIN_BACKOFF;
+
+    loop {
+        time::delay_for(backoff).await;
+
+        match connect().await {
+            Ok(ws) => return Ok(ws),
+            Err(e @ Error::StreamRefused(_)) => return Err(e),
+            Err(_) => backoff = std::cmp::min(backoff * 2, MAX_BACKOFF),
+        }
+    }
+}
+
+/// Open a websocket connection to `url`.
+async fn connect(url: &Url) -> Result<WebSocket> {
+    let (ws, _) = tokio_tungstenite::connect_async(url).await?;
+    Ok(ws)
+}
+
+/// Encode `message` as json.
+fn encode<T>(message: &T) -> Result<String>
+    where T: Serialize
+{
+    serde_json::to_string(message).map_err(Error::Encode)
+}
+
+/// Send `message` as a json text frame.
+async fn send<T>(ws: &mut WebSocket, message: &T) -> Result<()>
+    where T: Serialize
+{
+    ws.send(Message::Text(encode(message)?)).await?;
+    Ok(())
+}
+
+/// Wait for the next text or binary frame. Control frames are skipped.
+/// Returns `None` once the server closes the connection.
+async fn recv(ws: &mut WebSocket) -> Result<Option<String>> {
+    while let Some(message) = ws.next().await {
+        match message? {
+            Message::Text(text) => return Ok(Some(text)),
+            Message::Binary(data) => {
+                // The trading stream sends its json in binary frames.
+                return String::from_utf8(data)
+                    .map(Some)
+                    .map_err(|_| Error::Stream("received non utf-8 frame".into()));
+            },
+            Message::Close(_) => return Ok(None),
+            Message::Ping(_) | Message::Pong(_) => continue,
+        }
+    }
+
+    Ok(None)
+}
+
+/// Decode a json frame.
+fn decode<T>(text: String) -> Result<T>
+    where for<'de> T: serde::Deserialize<'de>
+{
+    serde_json::from_str(&text)
+        .map_err(|source| Error::Decode { source, body: text })
+}
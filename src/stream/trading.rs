@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use chrono::{DateTime, Duration, Utc};
+use futures::future::{self, AbortHandle, BoxFuture};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use url::Url;
+use uuid::Uuid;
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::model::{Order, OrderQuery, OrderQueryStatus, OrderStatus, TradeUpdate};
+
+use super::{Handler, WebSocket};
+
+/// Messages sent to the trading stream.
+#[derive(Serialize)]
+#[serde(tag = "action", content = "data", rename_all = "snake_case")]
+enum Request<'a> {
+    Authenticate {
+        key_id: &'a str,
+        secret_key: &'a str,
+    },
+    Listen {
+        streams: &'a [&'a str],
+    },
+}
+
+/// Messages received from the trading stream.
+#[derive(Deserialize)]
+#[serde(tag = "stream", content = "data", rename_all = "snake_case")]
+enum Response {
+    Authorization {
+        status: String,
+    },
+    Listening {},
+    TradeUpdates(Box<TradeUpdate>),
+}
+
+/// A stream of updates to the account's orders. Created with
+/// `Client::trade_updates`.
+///
+/// The connection is supervised in the background. Missed heartbeats
+/// and dropped connections are followed by a reconnect with exponential
+/// backoff, after which the stream is re-authenticated and re-subscribed.
+/// Orders that changed while the stream was offline are then reported
+/// as synthetic updates (see `TradeUpdate::synthetic`).
+///
+/// `Err` items report problems that did not stop the stream, such as a
+/// failure to catch up after a reconnect. The stream only ends if
+/// alpaca refuses to re-authenticate it.
+pub struct TradeUpdates {
+    receiver: mpsc::UnboundedReceiver<Result<TradeUpdate>>,
+    abort: AbortHandle,
+}
+
+impl Stream for TradeUpdates {
+    type Item = Result<TradeUpdate>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>)
+            -> Poll<Option<Self::Item>>
+    {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for TradeUpdates {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+impl Client {
+    /// The url of the trading stream, which lives next to the rest api.
+    fn trading_stream_endpoint(&self) -> Result<Url> {
+        let mut url = self.endpoint.join("/stream")?;
+        let scheme = match url.scheme() {
+            "http" => "ws",
+            _ => "wss",
+        };
+
+        url.set_scheme(scheme)
+            .map_err(|_| Error::Config(format!("cannot stream from {}", url)))?;
+
+        Ok(url)
+    }
+
+    /// Connect to the trading stream and subscribe to `trade_updates`.
+    /// Fails if the first connection cannot be established, or the open
+    /// orders cannot be listed.
+    pub async fn trade_updates(&self) -> Result<TradeUpdates> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let mut session = Session {
+            client: self.clone(),
+            url: self.trading_stream_endpoint()?,
+            streams: vec!["trade_updates"],
+            sender,
+            orders: HashMap::new(),
+        };
+
+        let ws = session.connect().await?;
+        session.track_open_orders().await?;
+        let (task, abort) = future::abortable(async move {
+            super::supervise(&mut session, ws, stream::pending()).await
+        });
+
+        tokio::spawn(task);
+        Ok(TradeUpdates { receiver, abort })
+    }
+}
+
+/// The last known state of an order.
+struct Tracked {
+    status: OrderStatus,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// The background half of `TradeUpdates`.
+struct Session {
+    client: Client,
+    url: Url,
+    streams: Vec<&'static str>,
+    sender: mpsc::UnboundedSender<Result<TradeUpdate>>,
+    /// Every open order reported so far, used to tell which orders
+    /// changed while the stream was offline.
+    orders: HashMap<Uuid, Tracked>,
+}
+
+impl Handler for Session {
+    type Command = Infallible;
+
+    /// Open, authenticate and subscribe a connection.
+    fn connect(&self) -> BoxFuture<'_, Result<WebSocket>> {
+        Box::pin(async move {
+            let mut ws = super::connect(&self.url).await?;
+
+            authenticate(&mut ws, &self.client.key_id, &self.client.secret_key).await?;
+            listen(&mut ws, &self.streams).await?;
+
+            Ok(ws)
+        })
+    }
+
+    fn frame(&mut self, text: String) -> bool {
+        let update = match super::decode(text) {
+            Ok(Response::TradeUpdates(update)) => Ok(*update),
+            Ok(_) => return true,
+            Err(e) => Err(e),
+        };
+
+        if let Ok(update) = &update {
+            self.track(&update.order);
+        }
+
+        self.sender.send(update).is_ok()
+    }
+
+    fn command(&mut self, command: Infallible) -> Result<String> {
+        match command {}
+    }
+
+    fn reconnected(&mut self, last_seen: DateTime<Utc>) -> BoxFuture<'_, ()> {
+        Box::pin(self.reconcile(last_seen))
+    }
+
+    fn fail(&mut self, error: Error) {
+        let _ = self.sender.send(Err(error));
+    }
+}
+
+impl Session {
+    /// Start tracking every open order, so that orders submitted before
+    /// the stream was opened are caught up on after a reconnect too.
+    async fn track_open_orders(&mut self) -> Result<()> {
+        let query = OrderQuery::new()
+            .status(OrderQueryStatus::Open)
+            .limit(500);
+
+        let orders: Vec<Order> = self.client.stream_orders(query).try_collect().await?;
+        for order in &orders {
+            self.track(order);
+        }
+
+        Ok(())
+    }
+
+    /// Report every order that changed since `since` as a synthetic
+    /// update. Orders that cannot be fetched are reported as errors, and
+    /// the rest are still caught up on.
+    async fn reconcile(&mut self, since: DateTime<Utc>) {
+        // Orders submitted while the stream was offline are new to us. The
+        // window is widened a little, in case the local clock is behind.
+        let query = OrderQuery::new()
+            .status(OrderQueryStatus::All)
+            .after(since - Duration::minutes(1))
+            .limit(500);
+
+        let mut orders = Vec::new();
+        let mut listing = Box::pin(self.client.stream_orders(query));
+        while let Some(order) = listing.next().await {
+            match order {
+                Ok(order) => orders.push(order),
+                Err(e) => {
+                    let _ = self.sender.send(Err(e));
+                    break;
+                },
+            }
+        }
+        drop(listing);
+
+        // Orders that were still live before the disconnect are fetched
+        // one by one.
+        let missing: Vec<Uuid> = self.orders.keys()
+            .copied()
+            .filter(|id| orders.iter().all(|order| order.id != *id))
+            .collect();
+
+        for id in missing {
+            match self.client.get_order(&id).await {
+                Ok(order) => orders.push(order),
+                Err(e) => {
+                    if self.sender.send(Err(e)).is_err() {
+                        return;
+                    }
+                },
+            }
+        }
+
+        // Report the oldest changes first.
+        orders.sort_by_key(|order| order.updated_at.unwrap_or(order.created_at));
+
+        for order in orders {
+            let changed = match self.orders.get(&order.id) {
+                Some(tracked) =>
+                    tracked.status != order.status
+                        || tracked.updated_at != order.updated_at,
+                None =>
+                    order.updated_at.unwrap_or(order.created_at) >= since,
+            };
+
+            if changed {
+                self.track(&order);
+                if self.sender.send(Ok(TradeUpdate::from_order(order))).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Remember the state of `order`. Orders that reached a terminal state
+    /// are forgotten instead, since they will not change again.
+    fn track(&mut self, order: &Order) {
+        if order.status.is_terminal() {
+            self.orders.remove(&order.id);
+            return;
+        }
+
+        self.orders.insert(order.id, Tracked {
+            status: order.status,
+            updated_at: order.updated_at,
+        });
+    }
+}
+
+/// Authenticate the connection, and wait for alpaca to accept it.
+async fn authenticate(ws: &mut WebSocket, key_id: &str, secret_key: &str)
+        -> Result<()>
+{
+    super::send(ws, &Request::Authenticate { key_id, secret_key }).await?;
+
+    loop {
+        match receive(ws).await? {
+            Response::Authorization { status } if status == "authorized" =>
+                return Ok(()),
+            Response::Authorization { status } =>
+                return Err(Error::StreamRefused(format!("authentication failed: {}", status))),
+            _ => continue,
+        }
+    }
+}
+
+/// Subscribe to `streams`, and wait for alpaca to confirm.
+async fn listen(ws: &mut WebSocket, streams: &[&str]) -> Result<()> {
+    super::send(ws, &Request::Listen { streams }).await?;
+
+    loop {
+        if let Response::Listening { .. } = receive(ws).await? {
+            return Ok(());
+        }
+    }
+}
+
+/// Receive the next message, failing if the connection is closed.
+async fn receive(ws: &mut WebSocket) -> Result<Response> {
+    match super::recv(ws).await? {
+        Some(text) => super::decode(text),
+        None => Err(Error::WebSocket(
+            tokio_tungstenite::tungstenite::Error::ConnectionClosed)),
+    }
+}